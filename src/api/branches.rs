@@ -23,6 +23,34 @@ impl Client {
         .await
     }
 
+    pub async fn get_protected_branch(&self, branch: &str) -> Result<Value> {
+        let encoded_branch = urlencoding::encode(branch);
+        self.get(&format!(
+            "/projects/{}/protected_branches/{}",
+            self.encoded_project(),
+            encoded_branch
+        ))
+        .await
+    }
+
+    /// Change settings of an already protected branch (GitLab 15.6+).
+    pub async fn update_protected_branch(
+        &self,
+        branch: &str,
+        allow_force_push: bool,
+    ) -> Result<Value> {
+        let encoded_branch = urlencoding::encode(branch);
+        self.patch(
+            &format!(
+                "/projects/{}/protected_branches/{}",
+                self.encoded_project(),
+                encoded_branch
+            ),
+            &serde_json::json!({ "allow_force_push": allow_force_push }),
+        )
+        .await
+    }
+
     pub async fn unprotect_branch(&self, branch: &str) -> Result<()> {
         let encoded_branch = urlencoding::encode(branch);
         self.delete(&format!(
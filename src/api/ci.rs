@@ -1,23 +1,60 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use serde_json::Value;
 
-use super::Client;
+use super::{ApiError, Client, HeaderMap};
+
+#[derive(Default)]
+pub struct PipelineListParams {
+    pub per_page: u32,
+    pub ref_name: Option<String>,
+    pub status: Option<String>,
+    pub updated_after: Option<String>,
+    pub updated_before: Option<String>,
+}
+
+impl PipelineListParams {
+    fn to_query(&self) -> String {
+        let mut query_parts = vec![format!("per_page={}", self.per_page)];
+
+        if let Some(ref_name) = &self.ref_name {
+            query_parts.push(format!("ref={}", urlencoding::encode(ref_name)));
+        }
+        if let Some(status) = &self.status {
+            query_parts.push(format!("status={}", urlencoding::encode(status)));
+        }
+        if let Some(after) = &self.updated_after {
+            query_parts.push(format!("updated_after={}", urlencoding::encode(after)));
+        }
+        if let Some(before) = &self.updated_before {
+            query_parts.push(format!("updated_before={}", urlencoding::encode(before)));
+        }
+
+        query_parts.join("&")
+    }
+}
 
 impl Client {
+    pub async fn list_pipelines(&self, params: &PipelineListParams) -> Result<(Value, HeaderMap)> {
+        self.get_with_headers(&format!(
+            "/projects/{}/pipelines?{}",
+            self.encoded_project(),
+            params.to_query()
+        ))
+        .await
+    }
+
     pub async fn list_pipelines_for_branch(
         &self,
         branch: Option<&str>,
         per_page: u32,
     ) -> Result<Value> {
-        let mut url = format!(
-            "/projects/{}/pipelines?per_page={}",
-            self.encoded_project(),
-            per_page
-        );
-        if let Some(ref_name) = branch {
-            url.push_str(&format!("&ref={}", urlencoding::encode(ref_name)));
-        }
-        self.get(&url).await
+        let params = PipelineListParams {
+            per_page,
+            ref_name: branch.map(str::to_string),
+            ..Default::default()
+        };
+        let (pipelines, _) = self.list_pipelines(&params).await?;
+        Ok(pipelines)
     }
 
     pub async fn get_pipeline(&self, id: u64) -> Result<Value> {
@@ -45,12 +82,12 @@ impl Client {
             self.encoded_project(),
             job_id
         );
-        let response = self.http.get(&url).send().await?;
+        let response = self.send(|http| http.get(&url)).await?;
         let status = response.status();
         let body = response.text().await?;
 
         if !status.is_success() {
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
 
         Ok(body)
@@ -76,6 +113,15 @@ impl Client {
         .await
     }
 
+    /// Validate `.gitlab-ci.yml` content in the context of the project.
+    pub async fn lint_ci_config(&self, content: &str) -> Result<Value> {
+        self.post(
+            &format!("/projects/{}/ci/lint", self.encoded_project()),
+            &serde_json::json!({ "content": content }),
+        )
+        .await
+    }
+
     pub async fn list_ci_variables(&self) -> Result<Value> {
         self.get(&format!(
             "/projects/{}/variables?per_page=100",
@@ -84,11 +130,19 @@ impl Client {
         .await
     }
 
-    pub async fn get_ci_variable(&self, key: &str) -> Result<Value> {
+    pub async fn get_ci_variable(
+        &self,
+        key: &str,
+        environment_scope: Option<&str>,
+    ) -> Result<Value> {
+        let scope_param = environment_scope
+            .map(|scope| format!("?filter[environment_scope]={}", urlencoding::encode(scope)))
+            .unwrap_or_default();
         self.get(&format!(
-            "/projects/{}/variables/{}",
+            "/projects/{}/variables/{}{}",
             self.encoded_project(),
-            urlencoding::encode(key)
+            urlencoding::encode(key),
+            scope_param
         ))
         .await
     }
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::{Client, HeaderMap};
+
+/// Whose activity to list.
+pub enum EventScope {
+    /// Everything that happened in the client's project
+    Project,
+    /// The authenticated user's own actions across all projects
+    CurrentUser,
+}
+
+impl Client {
+    /// Recent events, newest first. `after`/`before` are `YYYY-MM-DD` dates
+    /// and exclusive, as in the GitLab API.
+    pub async fn list_events(
+        &self,
+        scope: EventScope,
+        per_page: u32,
+        after: Option<&str>,
+        before: Option<&str>,
+    ) -> Result<(Value, HeaderMap)> {
+        let mut query = vec![format!("per_page={}", per_page)];
+        if let Some(after) = after {
+            query.push(format!("after={}", urlencoding::encode(after)));
+        }
+        if let Some(before) = before {
+            query.push(format!("before={}", urlencoding::encode(before)));
+        }
+        let path = match scope {
+            EventScope::Project => format!("/projects/{}/events", self.encoded_project()),
+            EventScope::CurrentUser => "/events".to_string(),
+        };
+        self.get_with_headers(&format!("{}?{}", path, query.join("&")))
+            .await
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde_json::Value;
 
-use super::Client;
+use super::{Client, HeaderMap};
 
 impl Client {
     pub async fn list_group_members(
@@ -26,6 +26,26 @@ impl Client {
         }
     }
 
+    /// Every direct member of a group, following pagination.
+    pub async fn list_direct_group_members(&self, group: &str) -> Result<Value> {
+        self.get_all_pages(&format!(
+            "/groups/{}/members?per_page=100",
+            urlencoding::encode(group)
+        ))
+        .await
+    }
+
+    /// List members including those inherited from parent groups, following
+    /// pagination so an audit sees every member.
+    pub async fn list_all_group_members(&self, group: &str, per_page: u32) -> Result<Value> {
+        let encoded_group = urlencoding::encode(group);
+        self.get_all_pages(&format!(
+            "/groups/{}/members/all?per_page={}",
+            encoded_group, per_page
+        ))
+        .await
+    }
+
     pub async fn list_group_subgroups(&self, group: &str, per_page: u32) -> Result<Value> {
         let encoded_group = urlencoding::encode(group);
         self.get(&format!(
@@ -40,6 +60,23 @@ impl Client {
         self.get(&format!("/groups/{}", encoded_group)).await
     }
 
+    /// Number of direct `resource` items (`projects`, `members`, `subgroups`)
+    /// in a group, from the `X-Total` header. `None` when GitLab omits it,
+    /// which it does for very large collections.
+    pub async fn count_group_resource(&self, group: &str, resource: &str) -> Result<Option<u64>> {
+        let encoded_group = urlencoding::encode(group);
+        let (_, headers) = self
+            .get_with_headers(&format!(
+                "/groups/{}/{}?per_page=1",
+                encoded_group, resource
+            ))
+            .await?;
+        Ok(headers
+            .get("x-total")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok()))
+    }
+
     pub async fn archive_project(&self, project: &str) -> Result<Value> {
         let encoded_project = urlencoding::encode(project);
         self.post(
@@ -58,6 +95,22 @@ impl Client {
         .await
     }
 
+    pub async fn delete_project(&self, project: &str) -> Result<Value> {
+        let encoded_project = urlencoding::encode(project);
+        self.delete_with_response(&format!("/projects/{}", encoded_project))
+            .await
+    }
+
+    /// Move a project into the namespace with ID `namespace`.
+    pub async fn transfer_project(&self, project: &str, namespace: u64) -> Result<Value> {
+        let encoded_project = urlencoding::encode(project);
+        self.put(
+            &format!("/projects/{}/transfer", encoded_project),
+            &serde_json::json!({ "namespace": namespace }),
+        )
+        .await
+    }
+
     pub async fn update_project(&self, project: &str, body: &Value) -> Result<Value> {
         let encoded_project = urlencoding::encode(project);
         self.put(&format!("/projects/{}", encoded_project), body)
@@ -69,6 +122,27 @@ impl Client {
         group: &str,
         per_page: u32,
         include_archived: bool,
+        statistics: bool,
+    ) -> Result<(Value, HeaderMap)> {
+        let encoded_group = urlencoding::encode(group);
+        let archived_param = if include_archived {
+            "&archived=true"
+        } else {
+            ""
+        };
+        let statistics_param = if statistics { "&statistics=true" } else { "" };
+        self.get_with_headers(&format!(
+            "/groups/{}/projects?per_page={}{}{}",
+            encoded_group, per_page, archived_param, statistics_param
+        ))
+        .await
+    }
+
+    /// Every project in a group, following pagination.
+    pub async fn list_all_group_projects(
+        &self,
+        group: &str,
+        include_archived: bool,
     ) -> Result<Value> {
         let encoded_group = urlencoding::encode(group);
         let archived_param = if include_archived {
@@ -76,9 +150,9 @@ impl Client {
         } else {
             ""
         };
-        self.get(&format!(
-            "/groups/{}/projects?per_page={}{}",
-            encoded_group, per_page, archived_param
+        self.get_all_pages(&format!(
+            "/groups/{}/projects?per_page=100{}",
+            encoded_group, archived_param
         ))
         .await
     }
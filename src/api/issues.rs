@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde_json::Value;
 
-use super::Client;
+use super::{assignee_query_param, Client, HeaderMap};
 
 #[derive(Default)]
 pub struct IssueListParams {
@@ -12,39 +12,93 @@ pub struct IssueListParams {
     pub labels: Option<String>,
     pub search: Option<String>,
     pub created_after: Option<String>,
+    pub weight: Option<String>,
+    pub due_date: Option<String>,
+    pub order_by: Option<String>,
+    pub sort: Option<String>,
 }
 
-impl Client {
-    pub async fn list_issues(&self, params: &IssueListParams) -> Result<Value> {
+impl IssueListParams {
+    fn to_query(&self) -> String {
         let mut query_parts = vec![
-            format!("per_page={}", params.per_page),
-            format!("state={}", params.state),
+            format!("per_page={}", self.per_page),
+            format!("state={}", self.state),
         ];
 
-        if let Some(author) = &params.author_username {
+        if let Some(author) = &self.author_username {
             query_parts.push(format!("author_username={}", urlencoding::encode(author)));
         }
-        if let Some(assignee) = &params.assignee_username {
-            query_parts.push(format!(
-                "assignee_username={}",
-                urlencoding::encode(assignee)
-            ));
+        if let Some(assignee) = &self.assignee_username {
+            query_parts.push(assignee_query_param(assignee));
         }
-        if let Some(labels) = &params.labels {
+        if let Some(labels) = &self.labels {
             query_parts.push(format!("labels={}", urlencoding::encode(labels)));
         }
-        if let Some(search) = &params.search {
+        if let Some(search) = &self.search {
             query_parts.push(format!("search={}", urlencoding::encode(search)));
         }
-        if let Some(after) = &params.created_after {
+        if let Some(after) = &self.created_after {
             query_parts.push(format!("created_after={}", urlencoding::encode(after)));
         }
+        if let Some(weight) = &self.weight {
+            query_parts.push(format!("weight={}", urlencoding::encode(weight)));
+        }
+        if let Some(due) = &self.due_date {
+            query_parts.push(format!("due_date={}", urlencoding::encode(due)));
+        }
+        if let Some(order) = &self.order_by {
+            query_parts.push(format!("order_by={}", order));
+        }
+        if let Some(sort) = &self.sort {
+            query_parts.push(format!("sort={}", sort));
+        }
 
-        let query = query_parts.join("&");
-        self.get(&format!(
+        query_parts.join("&")
+    }
+}
+
+impl Client {
+    pub async fn list_issues(&self, params: &IssueListParams) -> Result<(Value, HeaderMap)> {
+        self.get_with_headers(&format!(
             "/projects/{}/issues?{}",
             self.encoded_project(),
-            query
+            params.to_query()
+        ))
+        .await
+    }
+
+    /// Every issue matching `params`, following pagination; `per_page` is the page size.
+    pub async fn list_all_issues(&self, params: &IssueListParams) -> Result<Value> {
+        self.get_all_pages(&format!(
+            "/projects/{}/issues?{}",
+            self.encoded_project(),
+            params.to_query()
+        ))
+        .await
+    }
+
+    pub async fn list_all_group_issues(
+        &self,
+        group: &str,
+        params: &IssueListParams,
+    ) -> Result<Value> {
+        self.get_all_pages(&format!(
+            "/groups/{}/issues?{}",
+            urlencoding::encode(group),
+            params.to_query()
+        ))
+        .await
+    }
+
+    pub async fn list_group_issues(
+        &self,
+        group: &str,
+        params: &IssueListParams,
+    ) -> Result<(Value, HeaderMap)> {
+        self.get_with_headers(&format!(
+            "/groups/{}/issues?{}",
+            urlencoding::encode(group),
+            params.to_query()
         ))
         .await
     }
@@ -58,12 +112,49 @@ impl Client {
         .await
     }
 
+    /// Like `get_issue`, but `labels` holds label objects (name, color)
+    /// instead of names.
+    pub async fn get_issue_with_label_details(&self, iid: u64) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/issues/{}?with_labels_details=true",
+            self.encoded_project(),
+            iid
+        ))
+        .await
+    }
+
+    pub async fn subscribe_issue(&self, iid: u64) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/issues/{}/subscribe",
+                self.encoded_project(),
+                iid
+            ),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn unsubscribe_issue(&self, iid: u64) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/issues/{}/unsubscribe",
+                self.encoded_project(),
+                iid
+            ),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
     pub async fn create_issue(
         &self,
         title: &str,
         description: Option<&str>,
         labels: Option<&str>,
-        assignee: Option<&str>,
+        assignee_id: Option<u64>,
+        weight: Option<u64>,
+        due_date: Option<&str>,
     ) -> Result<Value> {
         let mut body = serde_json::json!({
             "title": title
@@ -75,8 +166,14 @@ impl Client {
         if let Some(lbls) = labels {
             body["labels"] = serde_json::Value::String(lbls.to_string());
         }
-        if let Some(user) = assignee {
-            body["assignee_username"] = serde_json::Value::String(user.to_string());
+        if let Some(id) = assignee_id {
+            body["assignee_ids"] = serde_json::json!([id]);
+        }
+        if let Some(weight) = weight {
+            body["weight"] = serde_json::json!(weight);
+        }
+        if let Some(due) = due_date {
+            body["due_date"] = serde_json::Value::String(due.to_string());
         }
 
         self.post(
@@ -85,4 +182,51 @@ impl Client {
         )
         .await
     }
+
+    pub async fn update_issue(&self, iid: u64, params: &Value) -> Result<Value> {
+        self.put(
+            &format!("/projects/{}/issues/{}", self.encoded_project(), iid),
+            params,
+        )
+        .await
+    }
+
+    pub async fn list_issue_links(&self, iid: u64) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/issues/{}/links",
+            self.encoded_project(),
+            iid
+        ))
+        .await
+    }
+
+    /// All discussions on an issue, following pagination; `per_page` is the page size.
+    pub async fn list_issue_discussions(&self, iid: u64, per_page: u32) -> Result<Value> {
+        self.get_all_pages(&format!(
+            "/projects/{}/issues/{}/discussions?per_page={}",
+            self.encoded_project(),
+            iid,
+            per_page
+        ))
+        .await
+    }
+
+    pub async fn create_issue_link(
+        &self,
+        iid: u64,
+        target_project: Option<&str>,
+        target_iid: u64,
+        link_type: &str,
+    ) -> Result<Value> {
+        let target_project = target_project.unwrap_or(&self.project);
+        self.post(
+            &format!("/projects/{}/issues/{}/links", self.encoded_project(), iid),
+            &serde_json::json!({
+                "target_project_id": target_project,
+                "target_issue_iid": target_iid,
+                "link_type": link_type
+            }),
+        )
+        .await
+    }
 }
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::Client;
+
+impl Client {
+    pub async fn create_label(
+        &self,
+        name: &str,
+        color: &str,
+        description: Option<&str>,
+    ) -> Result<Value> {
+        let mut body = serde_json::json!({ "name": name, "color": color });
+        if let Some(description) = description {
+            body["description"] = Value::String(description.to_string());
+        }
+        self.post(
+            &format!("/projects/{}/labels", self.encoded_project()),
+            &body,
+        )
+        .await
+    }
+}
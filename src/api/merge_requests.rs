@@ -1,51 +1,87 @@
 use anyhow::Result;
 use serde_json::Value;
 
-use super::Client;
+use super::{assignee_query_param, Client, HeaderMap};
 
 #[derive(Default)]
 pub struct MrListParams {
     pub per_page: u32,
     pub state: String,
     pub author_username: Option<String>,
+    pub assignee_username: Option<String>,
     pub created_after: Option<String>,
     pub created_before: Option<String>,
     pub updated_after: Option<String>,
     pub order_by: Option<String>,
     pub sort: Option<String>,
+    pub target_branch: Option<String>,
+    /// Only MRs that meet their approval requirements
+    pub approved: bool,
+    /// `Some(true)` for drafts only, `Some(false)` to exclude drafts
+    pub draft: Option<bool>,
 }
 
-impl Client {
-    pub async fn list_merge_requests(&self, params: &MrListParams) -> Result<Value> {
+impl MrListParams {
+    fn to_query(&self) -> String {
         let mut query_parts = vec![
-            format!("per_page={}", params.per_page),
-            format!("state={}", params.state),
+            format!("per_page={}", self.per_page),
+            format!("state={}", self.state),
         ];
 
-        if let Some(author) = &params.author_username {
+        if let Some(author) = &self.author_username {
             query_parts.push(format!("author_username={}", urlencoding::encode(author)));
         }
-        if let Some(after) = &params.created_after {
+        if let Some(assignee) = &self.assignee_username {
+            query_parts.push(assignee_query_param(assignee));
+        }
+        if let Some(after) = &self.created_after {
             query_parts.push(format!("created_after={}", urlencoding::encode(after)));
         }
-        if let Some(before) = &params.created_before {
+        if let Some(before) = &self.created_before {
             query_parts.push(format!("created_before={}", urlencoding::encode(before)));
         }
-        if let Some(after) = &params.updated_after {
+        if let Some(after) = &self.updated_after {
             query_parts.push(format!("updated_after={}", urlencoding::encode(after)));
         }
-        if let Some(order) = &params.order_by {
+        if let Some(order) = &self.order_by {
             query_parts.push(format!("order_by={}", order));
         }
-        if let Some(sort) = &params.sort {
+        if let Some(sort) = &self.sort {
             query_parts.push(format!("sort={}", sort));
         }
+        if let Some(target) = &self.target_branch {
+            query_parts.push(format!("target_branch={}", urlencoding::encode(target)));
+        }
+        if self.approved {
+            query_parts.push("approved=yes".to_string());
+        }
+        if let Some(draft) = self.draft {
+            query_parts.push(format!("wip={}", if draft { "yes" } else { "no" }));
+        }
 
-        let query = query_parts.join("&");
-        self.get(&format!(
+        query_parts.join("&")
+    }
+}
+
+impl Client {
+    pub async fn list_merge_requests(&self, params: &MrListParams) -> Result<(Value, HeaderMap)> {
+        self.get_with_headers(&format!(
             "/projects/{}/merge_requests?{}",
             self.encoded_project(),
-            query
+            params.to_query()
+        ))
+        .await
+    }
+
+    pub async fn list_group_merge_requests(
+        &self,
+        group: &str,
+        params: &MrListParams,
+    ) -> Result<(Value, HeaderMap)> {
+        self.get_with_headers(&format!(
+            "/groups/{}/merge_requests?{}",
+            urlencoding::encode(group),
+            params.to_query()
         ))
         .await
     }
@@ -59,6 +95,41 @@ impl Client {
         .await
     }
 
+    /// Like `get_merge_request`, but `labels` holds label objects (name, color)
+    /// instead of names.
+    pub async fn get_merge_request_with_label_details(&self, iid: u64) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/merge_requests/{}?with_labels_details=true",
+            self.encoded_project(),
+            iid
+        ))
+        .await
+    }
+
+    pub async fn subscribe_merge_request(&self, iid: u64) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/merge_requests/{}/subscribe",
+                self.encoded_project(),
+                iid
+            ),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    pub async fn unsubscribe_merge_request(&self, iid: u64) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/merge_requests/{}/unsubscribe",
+                self.encoded_project(),
+                iid
+            ),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
     pub async fn update_merge_request(&self, iid: u64, params: &Value) -> Result<Value> {
         self.put(
             &format!(
@@ -80,6 +151,27 @@ impl Client {
         .await
     }
 
+    /// Diff versions of an MR, one per push, newest first.
+    pub async fn list_mr_versions(&self, iid: u64) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/merge_requests/{}/versions",
+            self.encoded_project(),
+            iid
+        ))
+        .await
+    }
+
+    /// One MR version, with its `diffs` against the version's base commit.
+    pub async fn get_mr_version_diff(&self, iid: u64, version_id: u64) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/merge_requests/{}/versions/{}",
+            self.encoded_project(),
+            iid,
+            version_id
+        ))
+        .await
+    }
+
     pub async fn set_automerge(&self, iid: u64, remove_source_branch: bool) -> Result<Value> {
         self.put(
             &format!(
@@ -95,11 +187,43 @@ impl Client {
         .await
     }
 
-    pub async fn merge_merge_request(
-        &self,
-        iid: u64,
-        remove_source_branch: bool,
-    ) -> Result<Value> {
+    pub async fn cancel_automerge(&self, iid: u64) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/merge_requests/{}/cancel_merge_when_pipeline_succeeds",
+                self.encoded_project(),
+                iid
+            ),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    /// Add an MR to its target branch's merge train (Premium). Returns the
+    /// train cars GitLab reports for it.
+    pub async fn add_to_merge_train(&self, iid: u64) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/merge_trains/merge_requests/{}",
+                self.encoded_project(),
+                iid
+            ),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
+    /// Active cars on the merge train for `target_branch`, front of the train first.
+    pub async fn list_merge_train(&self, target_branch: &str) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/merge_trains/{}?scope=active&sort=asc",
+            self.encoded_project(),
+            urlencoding::encode(target_branch)
+        ))
+        .await
+    }
+
+    pub async fn merge_merge_request(&self, iid: u64, remove_source_branch: bool) -> Result<Value> {
         self.put(
             &format!(
                 "/projects/{}/merge_requests/{}/merge",
@@ -119,6 +243,8 @@ impl Client {
         source_branch: &str,
         target_branch: &str,
         description: Option<&str>,
+        assignee_id: Option<u64>,
+        reviewer_id: Option<u64>,
     ) -> Result<Value> {
         let mut body = serde_json::json!({
             "title": title,
@@ -129,6 +255,12 @@ impl Client {
         if let Some(desc) = description {
             body["description"] = serde_json::Value::String(desc.to_string());
         }
+        if let Some(id) = assignee_id {
+            body["assignee_ids"] = serde_json::json!([id]);
+        }
+        if let Some(id) = reviewer_id {
+            body["reviewer_ids"] = serde_json::json!([id]);
+        }
 
         self.post(
             &format!("/projects/{}/merge_requests", self.encoded_project()),
@@ -146,11 +278,19 @@ impl Client {
         .await
     }
 
-    pub async fn list_mr_notes(&self, iid: u64, per_page: u32) -> Result<Value> {
+    pub async fn list_mr_notes(
+        &self,
+        iid: u64,
+        per_page: u32,
+        order_by: &str,
+        sort: &str,
+    ) -> Result<Value> {
         self.get(&format!(
-            "/projects/{}/merge_requests/{}/notes?sort=desc&per_page={}",
+            "/projects/{}/merge_requests/{}/notes?order_by={}&sort={}&per_page={}",
             self.encoded_project(),
             iid,
+            order_by,
+            sort,
             per_page
         ))
         .await
@@ -168,17 +308,32 @@ impl Client {
         .await
     }
 
-    pub async fn approve_merge_request(&self, iid: u64) -> Result<()> {
-        self.post_empty(&format!(
-            "/projects/{}/merge_requests/{}/approve",
+    /// Approve an MR; GitLab rejects the approval with 409 if `sha` is no
+    /// longer the head of the source branch.
+    pub async fn approve_merge_request(&self, iid: u64, sha: &str) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/merge_requests/{}/approve",
+                self.encoded_project(),
+                iid
+            ),
+            &serde_json::json!({ "sha": sha }),
+        )
+        .await
+    }
+
+    pub async fn list_mr_approval_rules(&self, iid: u64) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/merge_requests/{}/approval_rules",
             self.encoded_project(),
             iid
         ))
         .await
     }
 
+    /// All discussions on an MR, following pagination; `per_page` is the page size.
     pub async fn list_mr_discussions(&self, iid: u64, per_page: u32) -> Result<Value> {
-        self.get(&format!(
+        self.get_all_pages(&format!(
             "/projects/{}/merge_requests/{}/discussions?per_page={}",
             self.encoded_project(),
             iid,
@@ -1,86 +1,373 @@
 mod branches;
 mod ci;
+mod events;
 mod groups;
 mod issues;
+mod labels;
 mod merge_requests;
 mod mirrors;
 mod raw;
+mod repository;
+mod runners;
+#[cfg(test)]
+pub(crate) mod test_server;
+mod time_tracking;
+mod tokens;
+mod uploads;
+mod users;
 mod webhooks;
+mod wiki;
 
-use anyhow::{anyhow, Context, Result};
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{RequestBuilder, Response, StatusCode};
 use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
 
+use crate::auth;
+use crate::config::Config;
+
+pub use reqwest::header::HeaderMap;
+
+pub use ci::PipelineListParams;
+pub use events::EventScope;
 pub use issues::IssueListParams;
 pub use merge_requests::MrListParams;
+pub use repository::CommitListParams;
+pub use time_tracking::TimeTarget;
 pub use webhooks::{WebhookCreateParams, WebhookUpdateParams};
 
+const API_PATH: &str = "/api/v4";
+
+/// Idle connections kept per host, so paginated fetches reuse one connection
+/// instead of reconnecting (and re-handshaking TLS) for every page.
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+/// A non-success HTTP response from the API. Displays as `HTTP <status>: <body>`;
+/// callers can downcast to it to branch on the status code.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl ApiError {
+    pub fn is_unauthorized(&self) -> bool {
+        self.status == StatusCode::UNAUTHORIZED
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status == StatusCode::NOT_FOUND
+    }
+
+    pub fn is_not_modified(&self) -> bool {
+        self.status == StatusCode::NOT_MODIFIED
+    }
+
+    pub fn is_forbidden(&self) -> bool {
+        self.status == StatusCode::FORBIDDEN
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        self.status == StatusCode::CONFLICT
+    }
+
+    pub fn is_bad_request(&self) -> bool {
+        self.status == StatusCode::BAD_REQUEST
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HTTP {}: {}", self.status, self.body)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// A request that took longer than `--request-timeout`. Pollers downcast to it
+/// to retry instead of giving up.
+#[derive(Debug)]
+pub struct RequestTimeout {
+    pub request: String,
+    pub limit: Duration,
+}
+
+impl std::fmt::Display for RequestTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} timed out after {:?} (--request-timeout)",
+            self.request, self.limit
+        )
+    }
+}
+
+impl std::error::Error for RequestTimeout {}
+
 pub struct Client {
     http: reqwest::Client,
     base_url: String,
     project: String,
+    token: RwLock<String>,
+    /// Config used to refresh an OAuth2 token the server rejects mid-session
+    refresh_config: Option<tokio::sync::Mutex<Config>>,
+    /// Username sent in the `Sudo` header so an admin token acts as that user
+    sudo: Option<String>,
+    /// Headers from `--header`, applied last so they win over the defaults
+    extra_headers: HeaderMap,
+    /// `--log-file`: one line per request, appended
+    log: Option<Mutex<std::fs::File>>,
+    /// `--request-timeout`: limit for each HTTP call, not for a command as a whole
+    timeout: Option<Duration>,
 }
 
 impl Client {
     pub fn new(host: &str, token: &str, project: &str) -> Result<Self> {
+        HeaderValue::from_str(&format!("Bearer {}", token)).context("Invalid auth token")?;
+
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&format!("Bearer {}", token)).context("Invalid auth token")?,
-        );
 
         let http = reqwest::Client::builder()
             .default_headers(headers)
+            .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
             .build()?;
 
-        let base_url = format!("{}/api/v4", host.trim_end_matches('/'));
+        let base_url = api_base_url(host);
 
         Ok(Self {
             http,
             base_url,
             project: project.to_string(),
+            token: RwLock::new(token.to_string()),
+            refresh_config: None,
+            sudo: None,
+            extra_headers: HeaderMap::new(),
+            log: None,
+            timeout: None,
         })
     }
 
+    /// Act as `username` on every request (admin tokens with the `sudo` scope).
+    pub fn with_sudo(mut self, username: Option<&str>) -> Result<Self> {
+        if let Some(username) = username {
+            HeaderValue::from_str(username).context("Invalid --sudo username")?;
+            self.sudo = Some(username.to_string());
+        }
+        Ok(self)
+    }
+
+    /// Send these headers with every request, replacing any of the same name.
+    pub fn with_headers(mut self, headers: &[(String, String)]) -> Result<Self> {
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("Invalid header name '{}'", name))?;
+            let value = HeaderValue::from_str(value)
+                .with_context(|| format!("Invalid value for header {}", name))?;
+            self.extra_headers.insert(name, value);
+        }
+        Ok(self)
+    }
+
+    /// Append a timestamped summary of every request to `path`: method,
+    /// URL with credentials redacted, status, and duration. No headers or bodies.
+    pub fn with_log_file(mut self, path: Option<&Path>) -> Result<Self> {
+        if let Some(path) = path {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open log file {}", path.display()))?;
+            self.log = Some(Mutex::new(file));
+        }
+        Ok(self)
+    }
+
+    /// Give up on any single request that takes longer than `timeout`. Polling
+    /// loops such as `ci wait` make many requests, each with its own limit.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Allow a 401 response to trigger one OAuth2 token refresh and a replay
+    /// of the request. The refreshed token is saved to the config file.
+    pub fn with_token_refresh(mut self, config: Config) -> Self {
+        if config.oauth2.is_some() {
+            self.refresh_config = Some(tokio::sync::Mutex::new(config));
+        }
+        self
+    }
+
     pub(crate) fn encoded_project(&self) -> String {
         urlencoding::encode(&self.project).into_owned()
     }
 
+    /// Send a request with the current token, refreshing and replaying it
+    /// once if the server answers 401.
+    pub(crate) async fn send(
+        &self,
+        build: impl Fn(&reqwest::Client) -> RequestBuilder,
+    ) -> Result<Response> {
+        let response = self.send_once(&build).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let Some(refresh_config) = &self.refresh_config else {
+            return Ok(response);
+        };
+
+        let mut config = refresh_config.lock().await;
+        eprintln!("Token rejected, refreshing...");
+        // A failed refresh leaves the 401 for the caller to report
+        if let Err(e) = auth::refresh_token(&mut config).await {
+            eprintln!("Token refresh failed: {:#}", e);
+            return Ok(response);
+        }
+        if let Some(token) = config.get_access_token() {
+            *self.token.write().unwrap() = token.to_string();
+        }
+        self.send_once(&build).await
+    }
+
+    async fn send_once(
+        &self,
+        build: &impl Fn(&reqwest::Client) -> RequestBuilder,
+    ) -> Result<Response> {
+        let token = self.token.read().unwrap().clone();
+        let mut request = build(&self.http).bearer_auth(token);
+        if let Some(username) = &self.sudo {
+            request = request.header("Sudo", username);
+        }
+        if !self.extra_headers.is_empty() {
+            request = request.headers(self.extra_headers.clone());
+        }
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        let request = request.build().context("Failed to build request")?;
+        let summary = format!("{} {}", request.method(), redact_url(request.url()));
+        let started = Instant::now();
+        let result = self.http.execute(request).await;
+        if let Some(log) = &self.log {
+            let outcome = match &result {
+                Ok(response) => response.status().to_string(),
+                Err(e) => format!("error: {}", e),
+            };
+            let line = format!(
+                "{} {} -> {} ({} ms)\n",
+                chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                summary,
+                outcome,
+                started.elapsed().as_millis()
+            );
+            // Logging must never fail the request itself
+            let _ = log.lock().unwrap().write_all(line.as_bytes());
+        }
+        let response = result.map_err(|e| match self.timeout {
+            Some(limit) if e.is_timeout() => RequestTimeout {
+                request: summary,
+                limit,
+            }
+            .into(),
+            _ => anyhow::Error::from(e).context("Failed to send request"),
+        })?;
+
+        if let Some(username) = &self.sudo {
+            if response.status() == StatusCode::FORBIDDEN {
+                let body = response.text().await?;
+                if body.to_lowercase().contains("sudo") {
+                    anyhow::bail!(
+                        "Cannot act as {}: --sudo needs an admin token with the sudo scope ({})",
+                        username,
+                        body
+                    );
+                }
+                return Err(ApiError {
+                    status: StatusCode::FORBIDDEN,
+                    body,
+                }
+                .into());
+            }
+        }
+        Ok(response)
+    }
+
     pub(crate) async fn get(&self, path: &str) -> Result<Value> {
+        let (value, _) = self.get_with_headers(path).await?;
+        Ok(value)
+    }
+
+    /// GET that also returns the response headers (e.g. `X-Total`, `X-Next-Page`).
+    pub(crate) async fn get_with_headers(&self, path: &str) -> Result<(Value, HeaderMap)> {
         let url = format!("{}{}", self.base_url, path);
-        let response = self
-            .http
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let response = self.send(|http| http.get(&url)).await?;
 
         let status = response.status();
+        let headers = response.headers().clone();
         let body = response.text().await?;
 
         if !status.is_success() {
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
 
-        serde_json::from_str(&body).context("Failed to parse JSON response")
+        let value = serde_json::from_str(&body).context("Failed to parse JSON response")?;
+        Ok((value, headers))
+    }
+
+    /// GET every page of a list endpoint by following `X-Next-Page`, returning
+    /// the concatenated array. `path` may already carry a query string.
+    pub(crate) async fn get_all_pages(&self, path: &str) -> Result<Value> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut page = 1u64;
+        loop {
+            let (value, headers) = self
+                .get_with_headers(&format!("{}{}page={}", path, separator, page))
+                .await?;
+            match value {
+                Value::Array(arr) => items.extend(arr),
+                other => return Ok(other),
+            }
+            let next = headers
+                .get("x-next-page")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+            match next {
+                Some(n) if n > page => page = n,
+                _ => break,
+            }
+        }
+        Ok(Value::Array(items))
     }
 
     pub(crate) async fn put(&self, path: &str, body: &Value) -> Result<Value> {
         let url = format!("{}{}", self.base_url, path);
-        let response = self
-            .http
-            .put(&url)
-            .json(body)
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let response = self.send(|http| http.put(&url).json(body)).await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(ApiError { status, body }.into());
+        }
+
+        serde_json::from_str(&body).context("Failed to parse JSON response")
+    }
+
+    pub(crate) async fn patch(&self, path: &str, body: &Value) -> Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.send(|http| http.patch(&url).json(body)).await?;
 
         let status = response.status();
         let body = response.text().await?;
 
         if !status.is_success() {
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
 
         serde_json::from_str(&body).context("Failed to parse JSON response")
@@ -88,19 +375,13 @@ impl Client {
 
     pub(crate) async fn post(&self, path: &str, body: &Value) -> Result<Value> {
         let url = format!("{}{}", self.base_url, path);
-        let response = self
-            .http
-            .post(&url)
-            .json(body)
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let response = self.send(|http| http.post(&url).json(body)).await?;
 
         let status = response.status();
         let body = response.text().await?;
 
         if !status.is_success() {
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
 
         serde_json::from_str(&body).context("Failed to parse JSON response")
@@ -108,17 +389,12 @@ impl Client {
 
     pub(crate) async fn post_empty(&self, path: &str) -> Result<()> {
         let url = format!("{}{}", self.base_url, path);
-        let response = self
-            .http
-            .post(&url)
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let response = self.send(|http| http.post(&url)).await?;
 
         let status = response.status();
         if !status.is_success() {
             let body = response.text().await?;
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
 
         Ok(())
@@ -126,26 +402,44 @@ impl Client {
 
     pub(crate) async fn delete(&self, path: &str) -> Result<()> {
         let url = format!("{}{}", self.base_url, path);
-        let response = self
-            .http
-            .delete(&url)
-            .send()
-            .await
-            .context("Failed to send request")?;
+        let response = self.send(|http| http.delete(&url)).await?;
 
         let status = response.status();
         if !status.is_success() {
             let body = response.text().await?;
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
         Ok(())
     }
 
+    /// DELETE that returns the response body, for endpoints that report
+    /// what happened (e.g. delayed deletion). An empty body yields `Null`.
+    pub(crate) async fn delete_with_response(&self, path: &str) -> Result<Value> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self.send(|http| http.delete(&url)).await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(ApiError { status, body }.into());
+        }
+        if body.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+
+        serde_json::from_str(&body).context("Failed to parse JSON response")
+    }
+
     pub async fn get_project(&self) -> Result<Value> {
         self.get(&format!("/projects/{}", self.encoded_project()))
             .await
     }
 
+    pub async fn get_gitlab_version(&self) -> Result<Value> {
+        self.get("/version").await
+    }
+
     pub async fn get_raw_file(&self, file_path: &str, git_ref: &str) -> Result<String> {
         let encoded_path = urlencoding::encode(file_path);
         let url = format!(
@@ -155,14 +449,154 @@ impl Client {
             encoded_path,
             urlencoding::encode(git_ref)
         );
-        let response = self.http.get(&url).send().await?;
+        let response = self.send(|http| http.get(&url)).await?;
         let status = response.status();
         let body = response.text().await?;
 
         if !status.is_success() {
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
 
         Ok(body)
     }
 }
+
+/// Build the assignee filter for list queries. `none` and `any` map to the
+/// special `assignee_id` values instead of a username match.
+pub(crate) fn assignee_query_param(assignee: &str) -> String {
+    match assignee.to_lowercase().as_str() {
+        "none" => "assignee_id=None".to_string(),
+        "any" => "assignee_id=Any".to_string(),
+        _ => format!("assignee_username={}", urlencoding::encode(assignee)),
+    }
+}
+
+/// A URL for logs, with the values of credential-like query parameters
+/// (`private_token`, `password`, `secret`, ...) replaced by `[REDACTED]`.
+fn redact_url(url: &reqwest::Url) -> String {
+    let sensitive = |key: &str| {
+        let key = key.to_lowercase();
+        ["token", "password", "secret"]
+            .iter()
+            .any(|word| key.contains(word))
+    };
+    if !url.query_pairs().any(|(key, _)| sensitive(&key)) {
+        return url.to_string();
+    }
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if sensitive(&key) {
+                "[REDACTED]".into()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted.to_string()
+}
+
+/// Build the API base URL from a host that may live under a subpath
+/// (e.g. `https://example.com/gitlab`) or already end in `/api/v4`.
+fn api_base_url(host: &str) -> String {
+    let host = host.trim_end_matches('/');
+    let host = host.strip_suffix(API_PATH).unwrap_or(host);
+    format!("{}{}", host, API_PATH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_server::{MockResponse, MockServer};
+    use super::*;
+
+    #[test]
+    fn api_base_url_supports_subpath_hosts() {
+        assert_eq!(
+            api_base_url("https://gitlab.com"),
+            "https://gitlab.com/api/v4"
+        );
+        assert_eq!(
+            api_base_url("https://example.com/gitlab/"),
+            "https://example.com/gitlab/api/v4"
+        );
+        assert_eq!(
+            api_base_url("https://example.com/gitlab/api/v4"),
+            "https://example.com/gitlab/api/v4"
+        );
+    }
+
+    #[test]
+    fn redact_url_hides_credentials_in_query() {
+        let url = reqwest::Url::parse(
+            "https://gitlab.com/api/v4/projects?private_token=glpat-secret&per_page=20",
+        )
+        .unwrap();
+        assert_eq!(
+            redact_url(&url),
+            "https://gitlab.com/api/v4/projects?private_token=%5BREDACTED%5D&per_page=20"
+        );
+
+        let plain = reqwest::Url::parse("https://gitlab.com/api/v4/projects/a%2Fb?page=2").unwrap();
+        assert_eq!(redact_url(&plain), plain.as_str());
+    }
+
+    /// A poller like `ci wait` runs far longer than the request timeout; only a
+    /// single slow request may fail.
+    #[tokio::test]
+    async fn request_timeout_applies_per_request() {
+        let server = MockServer::start(|request_line| {
+            let delay = if request_line.contains("/slow") {
+                1000
+            } else {
+                100
+            };
+            MockResponse::json("{}").delay(Duration::from_millis(delay))
+        })
+        .await;
+
+        // Eight polls take ~800ms in total, well past the 300ms limit
+        let client = Client::new(&server.url(), "token", "group/project")
+            .unwrap()
+            .with_timeout(Some(Duration::from_millis(300)));
+        let started = Instant::now();
+        for _ in 0..8 {
+            client.get("/poll").await.unwrap();
+        }
+        assert!(started.elapsed() > Duration::from_millis(300));
+
+        let err = client.get("/slow").await.unwrap_err();
+        assert!(err.downcast_ref::<RequestTimeout>().is_some(), "{}", err);
+        assert!(err.to_string().contains("timed out"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn get_all_pages_reuses_one_connection() {
+        const PAGES: u64 = 50;
+        let server = MockServer::start(|request_line| {
+            let page: u64 = request_line
+                .split("page=")
+                .last()
+                .and_then(|rest| rest.split(' ').next())
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(1);
+            let next = if page < PAGES {
+                (page + 1).to_string()
+            } else {
+                String::new()
+            };
+            MockResponse::json(format!("[{}]", page)).header("X-Next-Page", next)
+        })
+        .await;
+
+        let client = Client::new(&server.url(), "token", "group/project").unwrap();
+        let items = client.get_all_pages("/items?per_page=1").await.unwrap();
+
+        let items = items.as_array().unwrap();
+        assert_eq!(items.len(), PAGES as usize);
+        assert_eq!(items.last().unwrap(), PAGES);
+        assert_eq!(server.connections(), 1, "pages should share one connection");
+    }
+}
@@ -1,48 +1,132 @@
 use anyhow::{anyhow, Context, Result};
+use reqwest::Method;
 use serde_json::Value;
 
-use super::Client;
+use super::{ApiError, Client, API_PATH};
 
 impl Client {
     /// Make a raw API request. The endpoint can be with or without the `/api/v4/` prefix.
+    /// `data` is sent as a JSON body; non-empty `form` fields are sent
+    /// form-encoded instead.
     pub async fn raw_request(
         &self,
         method: &str,
         endpoint: &str,
         data: Option<&str>,
+        form: &[(String, String)],
     ) -> Result<String> {
-        let endpoint = endpoint.strip_prefix('/').unwrap_or(endpoint);
+        let url = raw_request_url(&self.base_url, endpoint);
 
-        let url = if endpoint.starts_with("api/v4/") {
-            format!("{}/{}", self.base_url.trim_end_matches("/api/v4"), endpoint)
-        } else {
-            format!("{}/{}", self.base_url, endpoint)
-        };
-
-        let builder = match method.to_uppercase().as_str() {
-            "GET" => self.http.get(&url),
-            "POST" => self.http.post(&url),
-            "PUT" => self.http.put(&url),
-            "DELETE" => self.http.delete(&url),
-            "PATCH" => self.http.patch(&url),
+        let method = match method.to_uppercase().as_str() {
+            "GET" => Method::GET,
+            "POST" => Method::POST,
+            "PUT" => Method::PUT,
+            "DELETE" => Method::DELETE,
+            "PATCH" => Method::PATCH,
             other => return Err(anyhow!("Unsupported HTTP method: {}", other)),
         };
 
-        let builder = if let Some(json_str) = data {
-            let body: Value = serde_json::from_str(json_str).context("Invalid JSON in --data")?;
-            builder.json(&body)
-        } else {
-            builder
-        };
+        let body: Option<Value> = data
+            .map(|json_str| serde_json::from_str(json_str).context("Invalid JSON in --data"))
+            .transpose()?;
+
+        let response = self
+            .send(|http| {
+                let builder = http.request(method.clone(), &url);
+                match &body {
+                    Some(body) => builder.json(body),
+                    None if !form.is_empty() => builder.form(form),
+                    None => builder,
+                }
+            })
+            .await?;
 
-        let response = builder.send().await.context("Failed to send request")?;
         let status = response.status();
         let body = response.text().await?;
 
         if !status.is_success() {
-            return Err(anyhow!("HTTP {}: {}", status, body));
+            return Err(ApiError { status, body }.into());
         }
 
         Ok(body)
     }
+
+    /// POST a query to the GraphQL endpoint and return the full response,
+    /// including any `errors` GitLab reports alongside `data`.
+    pub async fn graphql(&self, query: &str, variables: &Value) -> Result<Value> {
+        let url = graphql_url(&self.base_url);
+        let body = serde_json::json!({ "query": query, "variables": variables });
+        let response = self.send(|http| http.post(&url).json(&body)).await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(ApiError { status, body }.into());
+        }
+
+        serde_json::from_str(&body).context("Invalid JSON in GraphQL response")
+    }
+}
+
+/// GraphQL lives at `/api/graphql` next to the REST API, not under `/api/v4`.
+fn graphql_url(base_url: &str) -> String {
+    let web_root = base_url.strip_suffix(API_PATH).unwrap_or(base_url);
+    format!("{}/api/graphql", web_root)
+}
+
+/// Resolve an endpoint against the API base URL. Endpoints may be relative to
+/// `/api/v4`, start with `/api/v4/`, or include the instance subpath
+/// (e.g. `/gitlab/api/v4/projects` for a host of `https://example.com/gitlab`).
+fn raw_request_url(base_url: &str, endpoint: &str) -> String {
+    let web_root = base_url.strip_suffix(API_PATH).unwrap_or(base_url);
+    let subpath = reqwest::Url::parse(web_root)
+        .map(|u| u.path().trim_end_matches('/').to_string())
+        .unwrap_or_default();
+
+    let endpoint = format!("/{}", endpoint.trim_start_matches('/'));
+    let endpoint = if subpath.is_empty() {
+        endpoint.as_str()
+    } else {
+        endpoint
+            .strip_prefix(&subpath)
+            .filter(|rest| rest.starts_with(API_PATH))
+            .unwrap_or(&endpoint)
+    };
+
+    match endpoint.strip_prefix(API_PATH) {
+        Some(rest) if rest.starts_with('/') => format!("{}{}", base_url, rest),
+        _ => format!("{}{}", base_url, endpoint),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_request_url_handles_subpath_host() {
+        let base = "https://example.com/gitlab/api/v4";
+        let expected = "https://example.com/gitlab/api/v4/projects";
+        assert_eq!(raw_request_url(base, "projects"), expected);
+        assert_eq!(raw_request_url(base, "/projects"), expected);
+        assert_eq!(raw_request_url(base, "/api/v4/projects"), expected);
+        assert_eq!(raw_request_url(base, "/gitlab/api/v4/projects"), expected);
+    }
+
+    #[test]
+    fn raw_request_url_handles_root_host() {
+        let base = "https://gitlab.com/api/v4";
+        let expected = "https://gitlab.com/api/v4/projects";
+        assert_eq!(raw_request_url(base, "projects"), expected);
+        assert_eq!(raw_request_url(base, "api/v4/projects"), expected);
+    }
+
+    #[test]
+    fn graphql_url_sits_beside_rest_api() {
+        assert_eq!(
+            graphql_url("https://example.com/gitlab/api/v4"),
+            "https://example.com/gitlab/api/graphql"
+        );
+    }
 }
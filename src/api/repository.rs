@@ -0,0 +1,140 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::{Client, HeaderMap};
+
+pub struct CommitListParams {
+    pub per_page: u32,
+    pub ref_name: Option<String>,
+    pub author: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub path: Option<String>,
+}
+
+impl CommitListParams {
+    fn to_query(&self) -> String {
+        let mut query_parts = vec![format!("per_page={}", self.per_page)];
+
+        if let Some(ref_name) = &self.ref_name {
+            query_parts.push(format!("ref_name={}", urlencoding::encode(ref_name)));
+        }
+        if let Some(author) = &self.author {
+            query_parts.push(format!("author={}", urlencoding::encode(author)));
+        }
+        if let Some(since) = &self.since {
+            query_parts.push(format!("since={}", urlencoding::encode(since)));
+        }
+        if let Some(until) = &self.until {
+            query_parts.push(format!("until={}", urlencoding::encode(until)));
+        }
+        if let Some(path) = &self.path {
+            query_parts.push(format!("path={}", urlencoding::encode(path)));
+        }
+
+        query_parts.join("&")
+    }
+}
+
+impl Client {
+    pub async fn list_commits(&self, params: &CommitListParams) -> Result<(Value, HeaderMap)> {
+        self.get_with_headers(&format!(
+            "/projects/{}/repository/commits?{}",
+            self.encoded_project(),
+            params.to_query()
+        ))
+        .await
+    }
+
+    pub async fn compare_refs(&self, from: &str, to: &str) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/repository/compare?from={}&to={}",
+            self.encoded_project(),
+            urlencoding::encode(from),
+            urlencoding::encode(to)
+        ))
+        .await
+    }
+
+    /// Merge requests that contain (or were merged as) `sha`.
+    pub async fn list_commit_merge_requests(&self, sha: &str) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/repository/commits/{}/merge_requests",
+            self.encoded_project(),
+            urlencoding::encode(sha)
+        ))
+        .await
+    }
+
+    pub async fn list_repository_tree(&self, path: &str, git_ref: &str) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/repository/tree?path={}&ref={}&per_page=100",
+            self.encoded_project(),
+            urlencoding::encode(path),
+            urlencoding::encode(git_ref)
+        ))
+        .await
+    }
+
+    pub async fn cherry_pick_commit(&self, sha: &str, branch: &str) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/repository/commits/{}/cherry_pick",
+                self.encoded_project(),
+                urlencoding::encode(sha)
+            ),
+            &serde_json::json!({ "branch": branch }),
+        )
+        .await
+    }
+
+    pub async fn revert_commit(&self, sha: &str, branch: &str) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/repository/commits/{}/revert",
+                self.encoded_project(),
+                urlencoding::encode(sha)
+            ),
+            &serde_json::json!({ "branch": branch }),
+        )
+        .await
+    }
+
+    pub async fn get_commit_statuses(&self, sha: &str) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/repository/commits/{}/statuses",
+            self.encoded_project(),
+            urlencoding::encode(sha)
+        ))
+        .await
+    }
+
+    pub async fn set_commit_status(
+        &self,
+        sha: &str,
+        state: &str,
+        name: Option<&str>,
+        target_url: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<Value> {
+        let mut body = serde_json::json!({ "state": state });
+        if let Some(name) = name {
+            body["name"] = serde_json::json!(name);
+        }
+        if let Some(url) = target_url {
+            body["target_url"] = serde_json::json!(url);
+        }
+        if let Some(desc) = description {
+            body["description"] = serde_json::json!(desc);
+        }
+        self.post(
+            &format!(
+                "/projects/{}/statuses/{}",
+                self.encoded_project(),
+                urlencoding::encode(sha)
+            ),
+            &body,
+        )
+        .await
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::Client;
+
+impl Client {
+    /// Runners available to the current project.
+    pub async fn list_runners(&self) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/runners?per_page=100",
+            self.encoded_project()
+        ))
+        .await
+    }
+
+    pub async fn list_group_runners(&self, group: &str) -> Result<Value> {
+        self.get(&format!(
+            "/groups/{}/runners?per_page=100",
+            urlencoding::encode(group)
+        ))
+        .await
+    }
+
+    /// Runners owned by the authenticated user.
+    pub async fn list_user_runners(&self) -> Result<Value> {
+        self.get("/runners?per_page=100").await
+    }
+
+    pub async fn get_runner(&self, id: u64) -> Result<Value> {
+        self.get(&format!("/runners/{}", id)).await
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::Client;
+
+/// Issues and merge requests share the time-tracking endpoints.
+#[derive(Clone, Copy)]
+pub enum TimeTarget {
+    Issue,
+    MergeRequest,
+}
+
+impl TimeTarget {
+    fn path(self) -> &'static str {
+        match self {
+            TimeTarget::Issue => "issues",
+            TimeTarget::MergeRequest => "merge_requests",
+        }
+    }
+}
+
+impl Client {
+    pub async fn set_time_estimate(
+        &self,
+        target: TimeTarget,
+        iid: u64,
+        duration: &str,
+    ) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/{}/{}/time_estimate",
+                self.encoded_project(),
+                target.path(),
+                iid
+            ),
+            &serde_json::json!({ "duration": duration }),
+        )
+        .await
+    }
+
+    pub async fn add_spent_time(
+        &self,
+        target: TimeTarget,
+        iid: u64,
+        duration: &str,
+    ) -> Result<Value> {
+        self.post(
+            &format!(
+                "/projects/{}/{}/{}/add_spent_time",
+                self.encoded_project(),
+                target.path(),
+                iid
+            ),
+            &serde_json::json!({ "duration": duration }),
+        )
+        .await
+    }
+
+    pub async fn get_time_stats(&self, target: TimeTarget, iid: u64) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/{}/{}/time_stats",
+            self.encoded_project(),
+            target.path(),
+            iid
+        ))
+        .await
+    }
+}
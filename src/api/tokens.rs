@@ -0,0 +1,50 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::Client;
+
+impl Client {
+    /// Details of the personal access token used for this client.
+    pub async fn get_current_token(&self) -> Result<Value> {
+        self.get("/personal_access_tokens/self").await
+    }
+
+    pub async fn list_project_access_tokens(&self) -> Result<Value> {
+        self.get(&format!(
+            "/projects/{}/access_tokens?per_page=100",
+            self.encoded_project()
+        ))
+        .await
+    }
+
+    pub async fn create_project_access_token(
+        &self,
+        name: &str,
+        scopes: &[&str],
+        expires_at: Option<&str>,
+    ) -> Result<Value> {
+        let mut body = serde_json::json!({
+            "name": name,
+            "scopes": scopes
+        });
+
+        if let Some(date) = expires_at {
+            body["expires_at"] = serde_json::Value::String(date.to_string());
+        }
+
+        self.post(
+            &format!("/projects/{}/access_tokens", self.encoded_project()),
+            &body,
+        )
+        .await
+    }
+
+    pub async fn revoke_project_access_token(&self, token_id: u64) -> Result<()> {
+        self.delete(&format!(
+            "/projects/{}/access_tokens/{}",
+            self.encoded_project(),
+            token_id
+        ))
+        .await
+    }
+}
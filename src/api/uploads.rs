@@ -0,0 +1,41 @@
+use anyhow::{Context, Result};
+use reqwest::multipart::{Form, Part};
+use serde_json::Value;
+use std::path::Path;
+
+use super::{ApiError, Client};
+
+impl Client {
+    /// Upload a file to the project for use in comments and descriptions.
+    /// The response's `markdown` field is the snippet to embed.
+    pub async fn upload_file(&self, path: &Path) -> Result<Value> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "upload".to_string());
+        let url = format!(
+            "{}/projects/{}/uploads",
+            self.base_url,
+            self.encoded_project()
+        );
+
+        // Multipart forms are consumed on send, so build one per attempt
+        let response = self
+            .send(|http| {
+                let part = Part::bytes(bytes.clone()).file_name(file_name.clone());
+                http.post(&url).multipart(Form::new().part("file", part))
+            })
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(ApiError { status, body }.into());
+        }
+
+        serde_json::from_str(&body).context("Failed to parse JSON response")
+    }
+}
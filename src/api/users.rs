@@ -0,0 +1,77 @@
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+
+use super::Client;
+
+impl Client {
+    pub async fn get_current_user(&self) -> Result<Value> {
+        self.get("/user").await
+    }
+
+    pub async fn search_users(&self, query: &str) -> Result<Value> {
+        self.get(&format!("/users?search={}", urlencoding::encode(query)))
+            .await
+    }
+
+    pub async fn get_user_by_username(&self, username: &str) -> Result<Value> {
+        self.get(&format!(
+            "/users?username={}",
+            urlencoding::encode(username)
+        ))
+        .await
+    }
+
+    /// Resolve a username or email address to a user ID. Values containing `@`
+    /// are matched against the `email`/`public_email` fields of a user search.
+    /// A bare number is taken as the ID itself; prefix it with `@` to look it
+    /// up as a username instead.
+    pub async fn resolve_user_id(&self, user: &str) -> Result<u64> {
+        if let Ok(id) = user.parse() {
+            return Ok(id);
+        }
+        let user = user.trim_start_matches('@');
+        if !user.contains('@') {
+            let result = self.get_user_by_username(user).await?;
+            return result
+                .as_array()
+                .and_then(|users| users.first())
+                .and_then(|u| u["id"].as_u64())
+                .ok_or_else(|| anyhow!("User not found: {}", user));
+        }
+
+        let result = self.search_users(user).await?;
+        let matches: Vec<&Value> = result
+            .as_array()
+            .map(|users| {
+                users
+                    .iter()
+                    .filter(|u| {
+                        ["email", "public_email"].iter().any(|field| {
+                            u[*field]
+                                .as_str()
+                                .is_some_and(|e| e.eq_ignore_ascii_case(user))
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        match matches.as_slice() {
+            [] => bail!("No user found with email {}", user),
+            [u] => u["id"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("Invalid user ID for {}", user)),
+            _ => {
+                let names: Vec<&str> = matches
+                    .iter()
+                    .filter_map(|u| u["username"].as_str())
+                    .collect();
+                bail!(
+                    "Multiple users match {}: {} (use a username instead)",
+                    user,
+                    names.join(", ")
+                )
+            }
+        }
+    }
+}
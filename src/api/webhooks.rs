@@ -14,7 +14,13 @@ pub struct WebhookCreateParams {
     pub note_events: bool,
     pub job_events: bool,
     pub releases_events: bool,
-    pub enable_ssl_verification: bool,
+    pub wiki_page_events: bool,
+    pub deployment_events: bool,
+    pub confidential_issues_events: bool,
+    pub confidential_note_events: bool,
+    pub push_events_branch_filter: Option<String>,
+    /// `None` leaves GitLab's default (verify)
+    pub enable_ssl_verification: Option<bool>,
 }
 
 pub struct WebhookUpdateParams {
@@ -57,12 +63,21 @@ impl Client {
             "note_events": params.note_events,
             "job_events": params.job_events,
             "releases_events": params.releases_events,
-            "enable_ssl_verification": params.enable_ssl_verification
+            "wiki_page_events": params.wiki_page_events,
+            "deployment_events": params.deployment_events,
+            "confidential_issues_events": params.confidential_issues_events,
+            "confidential_note_events": params.confidential_note_events
         });
 
         if let Some(token) = &params.token {
             body["token"] = serde_json::Value::String(token.clone());
         }
+        if let Some(filter) = &params.push_events_branch_filter {
+            body["push_events_branch_filter"] = serde_json::Value::String(filter.clone());
+        }
+        if let Some(v) = params.enable_ssl_verification {
+            body["enable_ssl_verification"] = serde_json::Value::Bool(v);
+        }
 
         self.post(
             &format!("/projects/{}/hooks", self.encoded_project()),
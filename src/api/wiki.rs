@@ -0,0 +1,48 @@
+use anyhow::Result;
+use serde_json::Value;
+
+use super::Client;
+
+impl Client {
+    pub async fn list_wiki_pages(&self) -> Result<Value> {
+        self.get(&format!("/projects/{}/wikis", self.encoded_project()))
+            .await
+    }
+
+    pub async fn get_wiki_page(&self, slug: &str) -> Result<Value> {
+        let encoded_slug = urlencoding::encode(slug);
+        self.get(&format!(
+            "/projects/{}/wikis/{}",
+            self.encoded_project(),
+            encoded_slug
+        ))
+        .await
+    }
+
+    pub async fn create_wiki_page(
+        &self,
+        title: &str,
+        content: &str,
+        format: &str,
+    ) -> Result<Value> {
+        self.post(
+            &format!("/projects/{}/wikis", self.encoded_project()),
+            &serde_json::json!({
+                "title": title,
+                "content": content,
+                "format": format
+            }),
+        )
+        .await
+    }
+
+    pub async fn delete_wiki_page(&self, slug: &str) -> Result<()> {
+        let encoded_slug = urlencoding::encode(slug);
+        self.delete(&format!(
+            "/projects/{}/wikis/{}",
+            self.encoded_project(),
+            encoded_slug
+        ))
+        .await
+    }
+}
@@ -3,14 +3,15 @@ use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{Duration, Utc};
 use rand::Rng;
 use sha2::{Digest, Sha256};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
 use std::net::TcpListener;
+use std::time::Instant;
 
 use crate::config::{Config, OAuth2Config};
 
-const REDIRECT_URI: &str = "http://localhost:7171/auth/redirect";
-const LISTEN_ADDR: &str = "127.0.0.1:7171";
-const SCOPES: &str = "openid profile read_user write_repository api";
+pub const DEFAULT_REDIRECT_PORT: u16 = 7171;
+const CALLBACK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+pub const DEFAULT_SCOPES: &str = "openid profile read_user write_repository api";
 // Same client ID as glab for gitlab.com
 const DEFAULT_CLIENT_ID: &str = "41d48f9422ebd655dd9cf2947d6979681dfaddc6d0c56f7628f6ada59559af1e";
 
@@ -22,17 +23,28 @@ pub struct AuthFlow {
     host: String,
     client_id: String,
     code_verifier: String,
+    state: String,
+    redirect_port: u16,
+    scopes: String,
 }
 
 impl AuthFlow {
-    pub fn new(host: &str, client_id: &str) -> Self {
+    pub fn new(host: &str, client_id: &str, redirect_port: u16, scopes: &str) -> Self {
         Self {
             host: host.trim_end_matches('/').to_string(),
             client_id: client_id.to_string(),
-            code_verifier: generate_code_verifier(),
+            code_verifier: generate_random_token(),
+            state: generate_random_token(),
+            redirect_port,
+            scopes: scopes.to_string(),
         }
     }
 
+    /// Must match a redirect URI registered on the OAuth2 application.
+    fn redirect_uri(&self) -> String {
+        format!("http://localhost:{}/auth/redirect", self.redirect_port)
+    }
+
     fn code_challenge(&self) -> String {
         let mut hasher = Sha256::new();
         hasher.update(self.code_verifier.as_bytes());
@@ -42,37 +54,80 @@ impl AuthFlow {
     pub fn authorization_url(&self) -> String {
         let challenge = self.code_challenge();
         format!(
-            "{}/oauth/authorize?client_id={}&redirect_uri={}&response_type=code&scope={}&code_challenge={}&code_challenge_method=S256",
+            "{}/oauth/authorize?client_id={}&redirect_uri={}&response_type=code&scope={}&state={}&code_challenge={}&code_challenge_method=S256",
             self.host,
             urlencoding::encode(&self.client_id),
-            urlencoding::encode(REDIRECT_URI),
-            urlencoding::encode(SCOPES),
+            urlencoding::encode(&self.redirect_uri()),
+            urlencoding::encode(&self.scopes),
+            urlencoding::encode(&self.state),
             urlencoding::encode(&challenge),
         )
     }
 
     pub fn wait_for_callback(&self) -> Result<String> {
-        let listener = TcpListener::bind(LISTEN_ADDR)
-            .context("Failed to bind to port 7171. Is another instance running?")?;
+        let listener = TcpListener::bind(("127.0.0.1", self.redirect_port)).with_context(|| {
+            format!(
+                "Failed to bind to port {}. Is another instance running? Try --redirect-port",
+                self.redirect_port
+            )
+        })?;
 
-        println!("Waiting for authorization callback...");
+        listener
+            .set_nonblocking(true)
+            .context("Failed to configure callback listener")?;
 
-        let (mut stream, _) = listener.accept().context("Failed to accept connection")?;
-        let mut reader = BufReader::new(&stream);
-        let mut request_line = String::new();
-        reader.read_line(&mut request_line)?;
+        println!("Waiting for authorization callback...");
 
-        let code = extract_code_from_request(&request_line)?;
+        let deadline = Instant::now() + CALLBACK_TIMEOUT;
+        loop {
+            let mut stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        return Err(anyhow!(
+                            "Timed out after {}s waiting for authorization callback",
+                            CALLBACK_TIMEOUT.as_secs()
+                        ));
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                    continue;
+                }
+                Err(e) => return Err(e).context("Failed to accept connection"),
+            };
+            stream.set_nonblocking(false)?;
+            stream.set_read_timeout(Some(std::time::Duration::from_secs(5)))?;
+
+            let mut request_line = String::new();
+            if BufReader::new(&stream)
+                .read_line(&mut request_line)
+                .is_err()
+            {
+                continue;
+            }
 
-        let response = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n\
-            <html><body><h1>Authorization successful!</h1>\
-            <p>You can close this window and return to the terminal.</p></body></html>";
-        stream.write_all(response.as_bytes())?;
+            // Browsers may hit /favicon.ico or open speculative connections first
+            if !is_callback_request(&request_line) {
+                let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+                continue;
+            }
 
-        Ok(code)
+            let result = extract_code_from_request(&request_line, &self.state);
+            let response = if result.is_ok() {
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n\
+                    <html><body><h1>Authorization successful!</h1>\
+                    <p>You can close this window and return to the terminal.</p></body></html>"
+            } else {
+                "HTTP/1.1 400 Bad Request\r\nContent-Type: text/html\r\n\r\n\
+                    <html><body><h1>Authorization failed</h1>\
+                    <p>Return to the terminal for details.</p></body></html>"
+            };
+            let _ = stream.write_all(response.as_bytes());
+            return result;
+        }
     }
 
     pub async fn exchange_code(&self, code: &str) -> Result<OAuth2Config> {
+        let redirect_uri = self.redirect_uri();
         let client = reqwest::Client::new();
         let response = client
             .post(format!("{}/oauth/token", self.host))
@@ -80,7 +135,7 @@ impl AuthFlow {
                 ("client_id", self.client_id.as_str()),
                 ("code", code),
                 ("grant_type", "authorization_code"),
-                ("redirect_uri", REDIRECT_URI),
+                ("redirect_uri", &redirect_uri),
                 ("code_verifier", &self.code_verifier),
             ])
             .send()
@@ -94,7 +149,81 @@ impl AuthFlow {
             return Err(anyhow!("Token exchange failed: {}", body));
         }
 
-        parse_token_response(&self.client_id, &body)
+        parse_token_response(&self.client_id, &self.scopes, &body)
+    }
+}
+
+/// Log in with the OAuth2 device authorization grant: show a user code to
+/// enter on another device, then poll until the user approves.
+pub async fn device_login(host: &str, client_id: &str, scopes: &str) -> Result<OAuth2Config> {
+    let host = host.trim_end_matches('/');
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/oauth/authorize_device", host))
+        .form(&[("client_id", client_id), ("scope", scopes)])
+        .send()
+        .await
+        .context("Failed to start device authorization")?;
+
+    let status = response.status();
+    let body = response.text().await?;
+
+    if !status.is_success() {
+        return Err(anyhow!("Device authorization failed: {}", body));
+    }
+
+    let json: serde_json::Value =
+        serde_json::from_str(&body).context("Failed to parse device authorization response")?;
+    let device_code = json["device_code"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Missing device_code"))?;
+    let user_code = json["user_code"].as_str().unwrap_or("");
+    let verification_uri = json["verification_uri_complete"]
+        .as_str()
+        .or_else(|| json["verification_uri"].as_str())
+        .unwrap_or("");
+    let mut interval = json["interval"].as_u64().unwrap_or(5);
+    let expires_in = json["expires_in"].as_i64().unwrap_or(300);
+    let deadline = Utc::now() + Duration::seconds(expires_in);
+
+    println!("Visit: {}", verification_uri);
+    println!("Enter code: {}", user_code);
+    println!("Waiting for authorization...");
+
+    loop {
+        if Utc::now() >= deadline {
+            return Err(anyhow!("Device code expired before authorization"));
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+
+        let response = client
+            .post(format!("{}/oauth/token", host))
+            .form(&[
+                ("client_id", client_id),
+                ("device_code", device_code),
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ])
+            .send()
+            .await
+            .context("Failed to poll for device token")?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if status.is_success() {
+            return parse_token_response(client_id, scopes, &body);
+        }
+
+        let error: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        match error["error"].as_str() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => interval += 5,
+            Some("access_denied") => return Err(anyhow!("Authorization denied")),
+            Some("expired_token") => {
+                return Err(anyhow!("Device code expired before authorization"))
+            }
+            _ => return Err(anyhow!("Token exchange failed: {}", body)),
+        }
     }
 }
 
@@ -123,19 +252,36 @@ pub async fn refresh_token(config: &mut Config) -> Result<()> {
         return Err(anyhow!("Token refresh failed: {}", body));
     }
 
-    let new_oauth2 = parse_token_response(&oauth2.client_id, &body)?;
+    let scopes = oauth2.scopes.as_deref().unwrap_or(DEFAULT_SCOPES);
+    let new_oauth2 = parse_token_response(&oauth2.client_id, scopes, &body)?;
     config.oauth2 = Some(new_oauth2);
     config.save()?;
 
     Ok(())
 }
 
-fn generate_code_verifier() -> String {
+/// Random URL-safe string used for the PKCE code verifier and the `state` parameter.
+fn generate_random_token() -> String {
     let bytes: Vec<u8> = (0..32).map(|_| rand::thread_rng().gen()).collect();
     URL_SAFE_NO_PAD.encode(&bytes)
 }
 
-fn extract_code_from_request(request_line: &str) -> Result<String> {
+/// Whether a request line is the OAuth2 redirect (carrying `code` or `error`).
+fn is_callback_request(request_line: &str) -> bool {
+    let Some(path) = request_line.split_whitespace().nth(1) else {
+        return false;
+    };
+    let Some((route, query)) = path.split_once('?') else {
+        return false;
+    };
+    route == "/auth/redirect"
+        && query.split('&').any(|pair| {
+            let key = pair.split('=').next().unwrap_or("");
+            key == "code" || key == "error"
+        })
+}
+
+fn extract_code_from_request(request_line: &str, expected_state: &str) -> Result<String> {
     let parts: Vec<&str> = request_line.split_whitespace().collect();
     if parts.len() < 2 {
         return Err(anyhow!("Invalid HTTP request"));
@@ -147,34 +293,32 @@ fn extract_code_from_request(request_line: &str) -> Result<String> {
         .ok_or_else(|| anyhow!("No query string in callback"))?;
     let query = &path[query_start + 1..];
 
-    for pair in query.split('&') {
-        let mut kv = pair.splitn(2, '=');
-        if let (Some(key), Some(value)) = (kv.next(), kv.next()) {
-            if key == "code" {
-                return Ok(urlencoding::decode(value)?.into_owned());
-            }
-            if key == "error" {
-                let desc = query
-                    .split('&')
-                    .find_map(|p| {
-                        let mut kv = p.splitn(2, '=');
-                        if kv.next() == Some("error_description") {
-                            kv.next()
-                                .map(|v| urlencoding::decode(v).unwrap_or_default().into_owned())
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or_default();
-                return Err(anyhow!("Authorization failed: {} - {}", value, desc));
+    let param = |name: &str| {
+        query.split('&').find_map(|pair| {
+            let mut kv = pair.splitn(2, '=');
+            if kv.next() == Some(name) {
+                kv.next()
+                    .map(|v| urlencoding::decode(v).unwrap_or_default().into_owned())
+            } else {
+                None
             }
-        }
+        })
+    };
+
+    if let Some(error) = param("error") {
+        let desc = param("error_description").unwrap_or_default();
+        return Err(anyhow!("Authorization failed: {} - {}", error, desc));
     }
 
-    Err(anyhow!("No authorization code in callback"))
+    // Reject callbacks that weren't started by this login attempt (CSRF)
+    if param("state").as_deref() != Some(expected_state) {
+        return Err(anyhow!("OAuth2 state mismatch in callback; aborting login"));
+    }
+
+    param("code").ok_or_else(|| anyhow!("No authorization code in callback"))
 }
 
-fn parse_token_response(client_id: &str, body: &str) -> Result<OAuth2Config> {
+fn parse_token_response(client_id: &str, scopes: &str, body: &str) -> Result<OAuth2Config> {
     let json: serde_json::Value =
         serde_json::from_str(body).context("Failed to parse token response")?;
 
@@ -188,6 +332,9 @@ fn parse_token_response(client_id: &str, body: &str) -> Result<OAuth2Config> {
         .ok_or_else(|| anyhow!("Missing refresh_token"))?
         .to_string();
 
+    // GitLab reports the scopes actually granted, which may differ from the request
+    let granted_scopes = json["scope"].as_str().unwrap_or(scopes).to_string();
+
     let expires_in = json["expires_in"].as_i64().unwrap_or(7200);
     let expires_at = Utc::now() + Duration::seconds(expires_in);
 
@@ -196,5 +343,6 @@ fn parse_token_response(client_id: &str, body: &str) -> Result<OAuth2Config> {
         access_token,
         refresh_token,
         expires_at,
+        scopes: Some(granted_scopes),
     })
 }
@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project fields kept in the cache. Everything else (notably tokens such as
+/// `runners_token`) is dropped before writing to disk.
+const CACHED_PROJECT_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "path_with_namespace",
+    "description",
+    "visibility",
+    "default_branch",
+    "archived",
+    "web_url",
+    "last_activity_at",
+];
+
+#[derive(Serialize, Deserialize)]
+struct CachedProjects {
+    fetched_at: DateTime<Utc>,
+    projects: Value,
+}
+
+fn group_projects_file(dir: &Path, host: &str, group: &str, include_archived: bool) -> PathBuf {
+    let key: String = format!("{}_{}", host, group)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let suffix = if include_archived { "_archived" } else { "" };
+    dir.join(format!("group_projects_{}{}.json", key, suffix))
+}
+
+/// Cached project list for `group`, if present and younger than `ttl_secs`.
+pub fn load_group_projects(
+    dir: &Path,
+    host: &str,
+    group: &str,
+    include_archived: bool,
+    ttl_secs: u64,
+) -> Option<Value> {
+    let path = group_projects_file(dir, host, group, include_archived);
+    let content = fs::read_to_string(path).ok()?;
+    let cached: CachedProjects = serde_json::from_str(&content).ok()?;
+    let ttl = Duration::seconds(ttl_secs.try_into().unwrap_or(i64::MAX));
+    if Utc::now() - cached.fetched_at > ttl {
+        return None;
+    }
+    Some(cached.projects)
+}
+
+pub fn store_group_projects(
+    dir: &Path,
+    host: &str,
+    group: &str,
+    include_archived: bool,
+    projects: &Value,
+) -> Result<()> {
+    let stripped: Vec<Value> = projects
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .map(|p| {
+                    CACHED_PROJECT_FIELDS
+                        .iter()
+                        .filter_map(|&k| p.get(k).map(|v| (k.to_string(), v.clone())))
+                        .collect::<serde_json::Map<_, _>>()
+                        .into()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let cached = CachedProjects {
+        fetched_at: Utc::now(),
+        projects: Value::Array(stripped),
+    };
+    fs::create_dir_all(dir)?;
+    let path = group_projects_file(dir, host, group, include_archived);
+    fs::write(&path, serde_json::to_string(&cached)?)
+        .with_context(|| format!("Failed to write cache file {:?}", path))
+}
+
+/// Remove every cache file; returns how many were deleted.
+pub fn clear(dir: &Path) -> Result<usize> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", dir)),
+    };
+    let mut removed = 0;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
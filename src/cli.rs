@@ -1,9 +1,42 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::commands::print::OutputFormat;
 
 #[derive(Parser)]
 #[command(name = "gitlab")]
 #[command(about = "GitLab CLI for read-only operations")]
 pub struct Cli {
+    /// Path to config file (defaults to $GITLAB_CLI_CONFIG or the user config dir)
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    /// Output format for list commands: text, wide, json, yaml, or template:'{iid} {title}'
+    #[arg(long, global = true)]
+    pub output: Option<OutputFormat>,
+    /// Wrap JSON/YAML list output with pagination info (total, next_page)
+    #[arg(long, global = true)]
+    pub with_meta: bool,
+    /// Do not pipe long output through $GITLAB_CLI_PAGER / $PAGER
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+    /// Suppress confirmation messages of write commands (data and errors still print)
+    #[arg(long, global = true)]
+    pub quiet: bool,
+    /// Act as this user (admin token with the sudo scope required)
+    #[arg(long, global = true, value_name = "USERNAME")]
+    pub sudo: Option<String>,
+    /// Extra header sent with every API request (repeatable)
+    #[arg(long = "header", short = 'H', global = true, value_name = "KEY:VALUE")]
+    pub headers: Vec<String>,
+    /// Allow --header to replace the Authorization header
+    #[arg(long, global = true)]
+    pub force: bool,
+    /// Append a timestamped line per API request (method, redacted URL, status, time) to this file
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+    /// Fail any single API request that takes longer than this many seconds (0: no limit)
+    #[arg(long, global = true, value_name = "SECS")]
+    pub request_timeout: Option<u64>,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -12,7 +45,9 @@ pub struct Cli {
 pub enum Commands {
     /// Configure GitLab host, token, and default project
     Config {
-        /// GitLab host URL (e.g., https://gitlab.com)
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+        /// GitLab host URL (e.g., https://gitlab.com or https://example.com/gitlab)
         #[arg(long)]
         host: Option<String>,
         /// Personal access token
@@ -21,6 +56,15 @@ pub enum Commands {
         /// Default project (e.g., group/project)
         #[arg(long)]
         project: Option<String>,
+        /// Cache group project lists for this many seconds (0 disables)
+        #[arg(long)]
+        group_cache_ttl: Option<u64>,
+        /// Output format used when --output is not given (text, wide, json, yaml, template:...)
+        #[arg(long, value_name = "FORMAT")]
+        default_output: Option<String>,
+        /// Save --host/--project to .gitlab-cli.json at the repository root instead
+        #[arg(long, conflicts_with_all = ["token", "group_cache_ttl", "default_output"])]
+        local: bool,
     },
     /// Authentication commands
     Auth {
@@ -57,15 +101,70 @@ pub enum Commands {
         #[command(subcommand)]
         command: BranchCommands,
     },
+    /// Project access token commands
+    Token {
+        #[command(subcommand)]
+        command: TokenCommands,
+    },
+    /// CI runner commands
+    Runner {
+        #[command(subcommand)]
+        command: RunnerCommands,
+    },
     /// Webhook management commands
     Webhook {
         #[command(subcommand)]
         command: WebhookCommands,
     },
+    /// Project wiki commands
+    Wiki {
+        #[command(subcommand)]
+        command: WikiCommands,
+    },
+    /// Project label commands
+    Label {
+        #[command(subcommand)]
+        command: LabelCommands,
+    },
+    /// Commit commands
+    Commit {
+        #[command(subcommand)]
+        command: CommitCommands,
+    },
+    /// Recent activity (pushes, MR and issue actions, comments) in a project
+    Events {
+        /// Your own activity across all projects instead of one project's
+        #[arg(long, conflicts_with = "project")]
+        all: bool,
+        /// Only events after this date (YYYY-MM-DD, exclusive)
+        #[arg(long)]
+        after: Option<String>,
+        /// Only events before this date (YYYY-MM-DD, exclusive)
+        #[arg(long)]
+        before: Option<String>,
+        /// Number of results per page
+        #[arg(long, short = 'n', default_value = "20")]
+        per_page: u32,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Upload a file to the project and print its markdown link
+    Upload {
+        /// File to upload
+        path: PathBuf,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
     /// Fetch a raw file from a repository
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     File {
+        #[command(subcommand)]
+        command: Option<FileCommands>,
         /// File path in the repository (e.g., src/main.rs)
-        path: String,
+        #[arg(required = true)]
+        path: Option<String>,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -73,6 +172,45 @@ pub enum Commands {
         #[arg(long, name = "ref")]
         git_ref: Option<String>,
     },
+    /// Compare two refs (commits and diff stat)
+    Compare {
+        /// Base ref (branch, tag, or commit SHA)
+        from: String,
+        /// Ref to compare against the base
+        to: String,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Markdown list of MRs merged since a tag, for release notes
+    Changelog {
+        /// Tag (or other ref) of the previous release
+        #[arg(long)]
+        since: String,
+        /// End of the range (defaults to the project's default branch)
+        #[arg(long)]
+        to: Option<String>,
+        /// List commits instead of merge requests
+        #[arg(long)]
+        commits: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Define shortcuts for longer commands (e.g., mrs = mr list --mine)
+    Alias {
+        #[command(subcommand)]
+        command: AliasCommands,
+    },
+    /// Manage the local cache of group project lists
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+    /// Show CLI and GitLab instance versions
+    Version,
+    /// Check configuration, authentication, and connectivity
+    Doctor,
     /// Make a raw GitLab API call
     Api {
         /// API endpoint (e.g., /projects or /api/v4/projects)
@@ -81,8 +219,69 @@ pub enum Commands {
         #[arg(long, short, default_value = "GET")]
         method: String,
         /// JSON request body
-        #[arg(long, short)]
+        #[arg(long, short, conflicts_with = "form")]
         data: Option<String>,
+        /// Form field as KEY=VALUE, sent form-encoded (repeatable)
+        #[arg(long, short, value_name = "KEY=VALUE")]
+        form: Vec<String>,
+    },
+    /// Run a GraphQL query against /api/graphql
+    Graphql {
+        /// Query text, or @path to read it from a file
+        #[arg(long, short)]
+        query: String,
+        /// Query variable as key=value; values that parse as JSON are sent as JSON
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Open the config file in $EDITOR and validate it on save
+    Edit,
+}
+
+#[derive(Subcommand)]
+pub enum AliasCommands {
+    /// Define or replace an alias
+    Set {
+        /// Alias name, used in place of a command
+        name: String,
+        /// Arguments the alias expands to (e.g., mr list -s merged)
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        expansion: Vec<String>,
+    },
+    /// List defined aliases
+    List,
+    /// Remove an alias
+    Remove {
+        /// Alias name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Delete all cached data
+    Clear,
+}
+
+#[derive(Subcommand)]
+pub enum FileCommands {
+    /// List commits that touched a file
+    History {
+        /// File path in the repository (e.g., src/main.rs)
+        path: String,
+        /// Branch, tag, or SHA to walk back from (defaults to the default branch)
+        #[arg(long, name = "ref")]
+        git_ref: Option<String>,
+        /// Number of results per page
+        #[arg(long, short = 'n', default_value = "20")]
+        per_page: u32,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
     },
 }
 
@@ -96,6 +295,15 @@ pub enum AuthCommands {
         /// GitLab host URL (overrides configured host)
         #[arg(long)]
         host: Option<String>,
+        /// Local port for the OAuth2 callback (the app must allow http://localhost:<port>/auth/redirect)
+        #[arg(long, default_value_t = crate::auth::DEFAULT_REDIRECT_PORT)]
+        redirect_port: u16,
+        /// Use the device authorization grant (for machines without a browser)
+        #[arg(long, conflicts_with = "redirect_port")]
+        device: bool,
+        /// Space-separated OAuth2 scopes to request (e.g., "read_api read_user")
+        #[arg(long, default_value = crate::auth::DEFAULT_SCOPES)]
+        scopes: String,
     },
     /// Show authentication status
     Status,
@@ -111,6 +319,9 @@ pub enum MrCommands {
         /// Filter by author username
         #[arg(long, short)]
         author: Option<String>,
+        /// Filter by assignee username ("none" for unassigned, "any" for assigned)
+        #[arg(long)]
+        assignee: Option<String>,
         /// Filter by created after date (ISO 8601)
         #[arg(long)]
         created_after: Option<String>,
@@ -120,7 +331,19 @@ pub enum MrCommands {
         /// Filter by updated after date (ISO 8601)
         #[arg(long)]
         updated_after: Option<String>,
-        /// Order by: created_at, updated_at, merged_at
+        /// Filter by target branch
+        #[arg(long)]
+        target: Option<String>,
+        /// Only MRs that meet their approval requirements
+        #[arg(long)]
+        approved: bool,
+        /// Only draft MRs
+        #[arg(long, conflicts_with = "no_draft")]
+        draft: bool,
+        /// Exclude draft MRs
+        #[arg(long)]
+        no_draft: bool,
+        /// Order by: created_at, updated_at, merged_at, title
         #[arg(long, short)]
         order_by: Option<String>,
         /// Sort direction: asc, desc
@@ -129,6 +352,12 @@ pub enum MrCommands {
         /// Number of results per page
         #[arg(long, short = 'n', default_value = "20")]
         per_page: u32,
+        /// List merge requests across all projects in a group
+        #[arg(long, short, conflicts_with = "project")]
+        group: Option<String>,
+        /// Also show assignees and reviewers
+        #[arg(long, short)]
+        long: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -148,17 +377,30 @@ pub enum MrCommands {
         /// Keep source branch after merge
         #[arg(long)]
         keep_branch: bool,
+        /// Cancel a pending auto-merge instead of enabling it
+        #[arg(long, conflicts_with = "keep_branch")]
+        cancel: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
     },
-    /// Merge a merge request immediately
+    /// Merge one or more merge requests immediately
     Merge {
-        /// Merge request IID
-        iid: u64,
+        /// Merge request IIDs
+        #[arg(required_unless_present = "stdin")]
+        iids: Vec<u64>,
+        /// Read newline-separated IIDs from stdin
+        #[arg(long)]
+        stdin: bool,
         /// Keep source branch after merge
         #[arg(long)]
         keep_branch: bool,
+        /// Add to the merge train instead of merging directly
+        #[arg(long, conflicts_with = "keep_branch")]
+        train: bool,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -166,23 +408,47 @@ pub enum MrCommands {
     /// Create a new merge request
     Create {
         /// Merge request title
-        #[arg(long, short)]
-        title: String,
+        #[arg(long, short, required_unless_present_any = ["list_templates", "fill"])]
+        title: Option<String>,
+        /// Fill title and description from the commits on the source branch
+        #[arg(long)]
+        fill: bool,
+        /// Push the source branch to origin (git push -u) before creating
+        #[arg(long)]
+        push: bool,
         /// Merge request description
         #[arg(long, short)]
         description: Option<String>,
+        /// Use a description template from .gitlab/merge_request_templates
+        #[arg(long, conflicts_with = "description")]
+        template: Option<String>,
+        /// Open the description in $EDITOR before creating
+        #[arg(long, short)]
+        edit: bool,
+        /// List available description templates and exit
+        #[arg(long)]
+        list_templates: bool,
         /// Source branch (defaults to current branch)
         #[arg(long, short)]
         source: Option<String>,
         /// Target branch (defaults to default branch)
         #[arg(long)]
         target: Option<String>,
+        /// Assignee username, email, or numeric user ID
+        #[arg(long, short)]
+        assignee: Option<String>,
+        /// Reviewer username, email, or numeric user ID
+        #[arg(long, short)]
+        reviewer: Option<String>,
         /// Set to auto-merge when pipeline succeeds
         #[arg(long)]
         auto_merge: bool,
         /// Keep source branch after merge (only with --auto-merge)
         #[arg(long)]
         keep_branch: bool,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -194,18 +460,55 @@ pub enum MrCommands {
         /// Output as JSON instead of unified diff
         #[arg(long)]
         json: bool,
+        /// Show a diffstat summary instead of the full diff
+        #[arg(long, conflicts_with = "json")]
+        stat: bool,
+        /// Highlight changed words within lines instead of whole lines
+        #[arg(long, conflicts_with_all = ["json", "stat"])]
+        word_diff: bool,
+        /// Only print the paths of changed files, one per line
+        #[arg(long, conflicts_with_all = ["json", "stat", "word_diff"])]
+        name_only: bool,
+        /// Print changed paths prefixed with A, M, D, or R (added, modified, deleted, renamed)
+        #[arg(long, conflicts_with_all = ["json", "stat", "word_diff", "name_only"])]
+        name_status: bool,
+        /// Show the diff of this MR version (see `mr versions`) instead of the latest
+        #[arg(long)]
+        version: Option<u64>,
+        /// Only show what changed since this version, e.g. the one last reviewed
+        /// (up to --version, or the latest)
+        #[arg(long, value_name = "ID")]
+        since_version: Option<u64>,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
+        /// Only show changes under these paths (after `--`)
+        #[arg(last = true)]
+        paths: Vec<String>,
     },
-    /// Close a merge request
-    Close {
+    /// List the diff versions of a merge request (one per push)
+    Versions {
         /// Merge request IID
         iid: u64,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
     },
+    /// Close one or more merge requests
+    Close {
+        /// Merge request IIDs
+        #[arg(required_unless_present = "stdin")]
+        iids: Vec<u64>,
+        /// Read newline-separated IIDs from stdin
+        #[arg(long)]
+        stdin: bool,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
     /// List comments on a merge request
     Comments {
         /// Merge request IID
@@ -213,6 +516,18 @@ pub enum MrCommands {
         /// Number of comments to show
         #[arg(long, short = 'n', default_value = "10")]
         per_page: u32,
+        /// Order by: created_at, updated_at
+        #[arg(long, short, default_value = "created_at")]
+        order_by: String,
+        /// Sort direction: asc (oldest first), desc
+        #[arg(long, default_value = "desc")]
+        sort: String,
+        /// Include system notes (merges, label changes, pushes)
+        #[arg(long)]
+        system: bool,
+        /// Group comments by discussion thread, replies indented under their parent
+        #[arg(long, conflicts_with_all = ["order_by", "sort"])]
+        threaded: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -224,12 +539,57 @@ pub enum MrCommands {
         /// Comment body (reads from stdin if not provided)
         #[arg(long, short)]
         message: Option<String>,
+        /// Upload a file and append its markdown link to the comment (repeatable)
+        #[arg(long, value_name = "FILE")]
+        attach: Vec<PathBuf>,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
     },
-    /// Approve a merge request
+    /// Approve one or more merge requests
     Approve {
+        /// Merge request IIDs
+        #[arg(required_unless_present = "stdin")]
+        iids: Vec<u64>,
+        /// Read newline-separated IIDs from stdin
+        #[arg(long)]
+        stdin: bool,
+        /// Approve only if this is still the head commit (default: the head when fetched)
+        #[arg(long)]
+        sha: Option<String>,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Subscribe to notifications for a merge request
+    Subscribe {
+        /// Merge request IID
+        iid: u64,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Unsubscribe from notifications for a merge request
+    Unsubscribe {
+        /// Merge request IID
+        iid: u64,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Time tracking: estimates and spent time
+    Time {
+        #[command(subcommand)]
+        command: TimeCommands,
+    },
+    /// Show approval rules and their eligible approvers
+    ApprovalRules {
         /// Merge request IID
         iid: u64,
         /// Override default project
@@ -243,7 +603,7 @@ pub enum MrCommands {
         /// Only show unresolved threads
         #[arg(long, short)]
         unresolved: bool,
-        /// Number of discussions to fetch
+        /// Page size when fetching discussions
         #[arg(long, short = 'n', default_value = "50")]
         per_page: u32,
         /// Override default project
@@ -310,10 +670,78 @@ pub enum MrCommands {
         #[arg(long, short)]
         project: Option<String>,
     },
+    /// Resolve every unresolved discussion thread on a merge request
+    ResolveAll {
+        /// Merge request IID
+        iid: u64,
+        /// Unresolve every resolved thread instead
+        #[arg(long, short)]
+        unresolve_all: bool,
+        /// Page size when fetching discussions
+        #[arg(long, short = 'n', default_value = "100")]
+        per_page: u32,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TimeCommands {
+    /// Set the time estimate (e.g. 3h30m, 1d)
+    Estimate {
+        /// Issue or merge request IID
+        iid: u64,
+        /// Duration in GitLab format (e.g. 2h, 1w 2d, 30m)
+        duration: String,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Log time spent (negative durations subtract)
+    Spend {
+        /// Issue or merge request IID
+        iid: u64,
+        /// Duration in GitLab format (e.g. 30m, 1h15m, -10m)
+        #[arg(allow_hyphen_values = true)]
+        duration: String,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Show estimate vs time spent
+    Stats {
+        /// Issue or merge request IID
+        iid: u64,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum CiCommands {
+    /// List pipelines, newest first
+    List {
+        /// Only pipelines for this branch or tag
+        #[arg(long, short)]
+        branch: Option<String>,
+        /// Only pipelines with this status (e.g., failed, success, running)
+        #[arg(long, short)]
+        status: Option<String>,
+        /// Only pipelines updated after this date (YYYY-MM-DD or ISO 8601)
+        #[arg(long)]
+        updated_after: Option<String>,
+        /// Only pipelines updated before this date (YYYY-MM-DD or ISO 8601)
+        #[arg(long)]
+        updated_before: Option<String>,
+        /// Number of results per page
+        #[arg(long, short = 'n', default_value = "20")]
+        per_page: u32,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
     /// Show pipeline status
     Status {
         /// Pipeline ID (defaults to latest)
@@ -325,6 +753,15 @@ pub enum CiCommands {
         /// Merge request IID
         #[arg(long, short)]
         mr: Option<u64>,
+        /// With --mr, the latest pipeline of this type: branch, merge_request, merge_train
+        #[arg(long = "type", value_name = "TYPE", requires = "mr")]
+        pipeline_type: Option<String>,
+        /// Only show jobs with this status (e.g., failed, running, success)
+        #[arg(long)]
+        only: Option<String>,
+        /// Only show jobs whose name matches this glob (e.g., "test:*")
+        #[arg(long)]
+        name: Option<String>,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -340,6 +777,9 @@ pub enum CiCommands {
         /// Poll interval in seconds
         #[arg(long, default_value = "30")]
         interval: u64,
+        /// Give up after this many seconds (default: wait until the pipeline finishes)
+        #[arg(long)]
+        timeout: Option<u64>,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -365,6 +805,9 @@ pub enum CiCommands {
         /// Retry entire pipeline instead of a single job
         #[arg(long)]
         pipeline: bool,
+        /// With --pipeline, retry each failed job individually
+        #[arg(long, requires = "pipeline")]
+        failed_only: bool,
         /// Branch name (defaults to current git branch)
         #[arg(long, short)]
         branch: Option<String>,
@@ -372,10 +815,22 @@ pub enum CiCommands {
         #[arg(long, short)]
         project: Option<String>,
     },
+    /// Validate a CI config file against the project
+    Lint {
+        /// CI config to check ("-" reads stdin)
+        #[arg(long, short, default_value = ".gitlab-ci.yml")]
+        file: PathBuf,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
     /// Manage CI/CD variables
     Vars {
         #[command(subcommand)]
         command: Option<VarsCommands>,
+        /// Only list variables whose environment scope matches this glob (e.g., "review/*")
+        #[arg(long)]
+        env: Option<String>,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -388,6 +843,12 @@ pub enum VarsCommands {
     Get {
         /// Variable key name
         key: String,
+        /// Environment scope to fetch when the key has several scoped values
+        #[arg(long)]
+        env: Option<String>,
+        /// For file-type variables, base64-decode the content when it is encoded
+        #[arg(long)]
+        decode: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -400,12 +861,15 @@ pub enum GroupCommands {
     Members {
         /// Group path (e.g., globalcomix)
         group: String,
-        /// Number of results per page
+        /// Number of results per page (with --all-levels: page size, every page is fetched)
         #[arg(long, short = 'n', default_value = "100")]
         per_page: u32,
         /// Show email addresses (requires admin access)
         #[arg(long, short)]
         email: bool,
+        /// Include members inherited from parent groups
+        #[arg(long, conflicts_with = "email")]
+        all_levels: bool,
     },
     /// List subgroups
     Subgroups {
@@ -419,6 +883,9 @@ pub enum GroupCommands {
     Show {
         /// Group path (e.g., globalcomix)
         group: String,
+        /// Print the raw API response
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -434,6 +901,25 @@ pub enum ProjectCommands {
         /// Project path (e.g., group/project)
         project: String,
     },
+    /// Delete a project (asks to type the project path to confirm)
+    Delete {
+        /// Project path (e.g., group/project)
+        project: String,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Move a project to another group (asks to type the project path to confirm)
+    Transfer {
+        /// Project path (e.g., group/project)
+        project: String,
+        /// Target group path (e.g., group/subgroup)
+        #[arg(long)]
+        to: String,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
     /// List projects in a group
     List {
         /// Group path (e.g., globalcomix)
@@ -441,9 +927,12 @@ pub enum ProjectCommands {
         /// Include archived projects (excluded by default)
         #[arg(long, short)]
         archived: bool,
-        /// Number of results per page
-        #[arg(long, short = 'n', default_value = "50")]
-        per_page: u32,
+        /// Also show last activity and storage size (needs maintainer access)
+        #[arg(long)]
+        with_stats: bool,
+        /// Number of results per page [default: 50]; bypasses the group cache
+        #[arg(long, short = 'n')]
+        per_page: Option<u32>,
     },
     /// Update project settings
     Update {
@@ -529,6 +1018,9 @@ pub enum MirrorCommands {
         project: String,
         /// Mirror ID to remove
         mirror_id: u64,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
     },
     /// Trigger a push mirror sync
     Sync {
@@ -554,6 +1046,9 @@ pub enum BranchCommands {
         /// Allow force push
         #[arg(long)]
         allow_force_push: bool,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -568,6 +1063,194 @@ pub enum BranchCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum CommitCommands {
+    /// List commits
+    List {
+        /// Branch, tag, or SHA to list from (defaults to the default branch)
+        #[arg(long, name = "ref")]
+        ref_name: Option<String>,
+        /// Filter by author name or email
+        #[arg(long, short)]
+        author: Option<String>,
+        /// Only commits after this date (YYYY-MM-DD or ISO 8601)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only commits before this date (YYYY-MM-DD or ISO 8601)
+        #[arg(long)]
+        until: Option<String>,
+        /// Only commits touching this path
+        #[arg(long)]
+        path: Option<String>,
+        /// Number of results per page
+        #[arg(long, short = 'n', default_value = "20")]
+        per_page: u32,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Cherry-pick a commit onto a branch
+    CherryPick {
+        /// Commit SHA to cherry-pick
+        sha: String,
+        /// Target branch
+        #[arg(long, short)]
+        branch: String,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Revert a commit on a branch
+    Revert {
+        /// Commit SHA to revert
+        sha: String,
+        /// Target branch
+        #[arg(long, short)]
+        branch: String,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Show commit statuses, or report one with --state
+    Status {
+        /// Commit SHA
+        sha: String,
+        /// Set a status: pending, running, success, failed, canceled
+        #[arg(long, short)]
+        state: Option<String>,
+        /// Status name (context), e.g. my-check
+        #[arg(long, short, requires = "state")]
+        name: Option<String>,
+        /// URL to link from the status
+        #[arg(long, short, requires = "state")]
+        target_url: Option<String>,
+        /// Short description of the status
+        #[arg(long, short, requires = "state")]
+        description: Option<String>,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TokenCommands {
+    /// List project access tokens
+    List {
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Create a project access token
+    Create {
+        /// Token name
+        name: String,
+        /// Scopes (comma-separated, e.g., read_api,read_repository)
+        #[arg(long, short)]
+        scopes: String,
+        /// Expiry date (YYYY-MM-DD)
+        #[arg(long, short)]
+        expires_at: Option<String>,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Revoke a project access token
+    Revoke {
+        /// Token ID
+        id: u64,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RunnerCommands {
+    /// List runners for the project (or a group, or all runners you own)
+    List {
+        /// List a group's runners instead
+        #[arg(long, short, conflicts_with_all = ["project", "all"])]
+        group: Option<String>,
+        /// List every runner you have access to
+        #[arg(long, short, conflicts_with = "project")]
+        all: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Show runner details
+    Show {
+        /// Runner ID
+        id: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WikiCommands {
+    /// List wiki pages
+    List {
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Show a wiki page
+    Show {
+        /// Page slug (see `wiki list`)
+        slug: String,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Create a wiki page from a file or stdin
+    Create {
+        /// Page title
+        #[arg(long, short)]
+        title: String,
+        /// Read content from this file instead of stdin
+        #[arg(long, short)]
+        file: Option<PathBuf>,
+        /// Markup format: markdown, rdoc, asciidoc, org
+        #[arg(long, default_value = "markdown")]
+        format: String,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Delete a wiki page
+    Delete {
+        /// Page slug
+        slug: String,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LabelCommands {
+    /// Create labels from a JSON file, skipping ones that already exist
+    Import {
+        /// JSON array of {"name", "color", "description"} objects
+        file: PathBuf,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum WebhookCommands {
     /// List webhooks for a project
@@ -587,11 +1270,17 @@ pub enum WebhookCommands {
     /// Create a new webhook
     Create {
         /// Webhook URL
-        #[arg(long, short)]
-        url: String,
+        #[arg(long, short, required_unless_present = "from")]
+        url: Option<String>,
         /// Secret token for webhook verification
         #[arg(long, short)]
         token: Option<String>,
+        /// Copy the URL, events, branch filter, and SSL setting of an existing webhook
+        #[arg(long, value_name = "HOOK_ID")]
+        from: Option<u64>,
+        /// Project of the --from webhook (defaults to the target project)
+        #[arg(long, requires = "from")]
+        from_project: Option<String>,
         /// Enable push events
         #[arg(long)]
         push: bool,
@@ -616,9 +1305,30 @@ pub enum WebhookCommands {
         /// Enable release events
         #[arg(long)]
         release: bool,
-        /// Enable SSL verification
-        #[arg(long, default_value = "true")]
-        ssl_verification: bool,
+        /// Enable wiki page events
+        #[arg(long)]
+        wiki_page: bool,
+        /// Enable deployment events
+        #[arg(long)]
+        deployment: bool,
+        /// Enable confidential issue events
+        #[arg(long)]
+        confidential_issue: bool,
+        /// Enable confidential note events
+        #[arg(long)]
+        confidential_note: bool,
+        /// Only send push events for branches matching this wildcard
+        #[arg(long, value_name = "PATTERN")]
+        branch_filter: Option<String>,
+        /// Enable SSL verification (default: true, or the --from hook's setting)
+        #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+        ssl_verification: Option<bool>,
+        /// Send a HEAD request to the URL first and fail if it can't be reached
+        #[arg(long)]
+        verify_url: bool,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -668,6 +1378,9 @@ pub enum WebhookCommands {
     Delete {
         /// Webhook ID
         id: u64,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -695,7 +1408,7 @@ pub enum IssueCommands {
         /// Filter by author username
         #[arg(long, short)]
         author: Option<String>,
-        /// Filter by assignee username
+        /// Filter by assignee username ("none" for unassigned, "any" for assigned)
         #[arg(long)]
         assignee: Option<String>,
         /// Filter by labels (comma-separated)
@@ -707,9 +1420,45 @@ pub enum IssueCommands {
         /// Filter by created after date (ISO 8601)
         #[arg(long)]
         created_after: Option<String>,
+        /// Filter by weight: a number, "none", or "any"
+        #[arg(long)]
+        weight: Option<String>,
+        /// Filter by due date: none, any, today, tomorrow, overdue, week, month
+        #[arg(long)]
+        due: Option<String>,
+        /// Order by: created_at, updated_at, priority, due_date
+        #[arg(long, short)]
+        order_by: Option<String>,
+        /// Sort direction: asc, desc
+        #[arg(long)]
+        sort: Option<String>,
         /// Number of results per page
         #[arg(long, short = 'n', default_value = "20")]
         per_page: u32,
+        /// List issues across all projects in a group
+        #[arg(long, short, conflicts_with = "project")]
+        group: Option<String>,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Show issues grouped by label, assignee, or milestone
+    Board {
+        /// Group by: label, assignee, milestone
+        #[arg(long, default_value = "label")]
+        group_by: String,
+        /// Filter by state: opened, closed, all
+        #[arg(long, short, default_value = "opened")]
+        state: String,
+        /// Filter by labels (comma-separated)
+        #[arg(long, short)]
+        labels: Option<String>,
+        /// Filter by assignee username ("none" for unassigned, "any" for assigned)
+        #[arg(long)]
+        assignee: Option<String>,
+        /// Show issues across all projects in a group
+        #[arg(long, short, conflicts_with = "project")]
+        group: Option<String>,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
@@ -725,17 +1474,109 @@ pub enum IssueCommands {
     /// Create a new issue
     Create {
         /// Issue title
-        #[arg(long, short)]
-        title: String,
+        #[arg(long, short, required_unless_present = "list_templates")]
+        title: Option<String>,
         /// Issue description
         #[arg(long, short)]
         description: Option<String>,
+        /// Use a description template from .gitlab/issue_templates
+        #[arg(long, conflicts_with = "description")]
+        template: Option<String>,
+        /// Open the description in $EDITOR before creating
+        #[arg(long, short)]
+        edit: bool,
+        /// List available description templates and exit
+        #[arg(long)]
+        list_templates: bool,
         /// Labels (comma-separated)
         #[arg(long, short)]
         labels: Option<String>,
-        /// Assignee username
+        /// Assignee username, email, or numeric user ID
         #[arg(long, short)]
         assignee: Option<String>,
+        /// Issue weight
+        #[arg(long)]
+        weight: Option<u64>,
+        /// Due date (YYYY-MM-DD)
+        #[arg(long)]
+        due_date: Option<String>,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Change the weight or due date of an issue
+    Update {
+        /// Issue IID
+        iid: u64,
+        /// Issue weight
+        #[arg(long)]
+        weight: Option<u64>,
+        /// Due date (YYYY-MM-DD, or "none" to clear it)
+        #[arg(long)]
+        due_date: Option<String>,
+        /// Shorthand for `--output json`
+        #[arg(long)]
+        json: bool,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Link an issue to another issue
+    Link {
+        /// Issue IID
+        iid: u64,
+        /// Target issue IID
+        #[arg(long)]
+        to: u64,
+        /// Link type: relates_to, blocks, is_blocked_by
+        #[arg(long, name = "type", default_value = "relates_to")]
+        link_type: String,
+        /// Project of the target issue (defaults to the same project)
+        #[arg(long)]
+        to_project: Option<String>,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// List issues linked to an issue
+    Links {
+        /// Issue IID
+        iid: u64,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Subscribe to notifications for an issue
+    Subscribe {
+        /// Issue IID
+        iid: u64,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Unsubscribe from notifications for an issue
+    Unsubscribe {
+        /// Issue IID
+        iid: u64,
+        /// Override default project
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+    /// Time tracking: estimates and spent time
+    Time {
+        #[command(subcommand)]
+        command: TimeCommands,
+    },
+    /// List discussion threads on an issue
+    Discussions {
+        /// Issue IID
+        iid: u64,
+        /// Page size when fetching discussions
+        #[arg(long, short = 'n', default_value = "50")]
+        per_page: u32,
         /// Override default project
         #[arg(long, short)]
         project: Option<String>,
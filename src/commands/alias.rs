@@ -0,0 +1,115 @@
+use anyhow::{bail, Result};
+use clap::CommandFactory;
+use std::collections::BTreeMap;
+
+use crate::cli::{AliasCommands, Cli};
+use crate::config::Config;
+
+/// Expansions allowed before giving up, so a long alias chain can't spin
+const MAX_ALIAS_DEPTH: usize = 10;
+
+/// Global options that take a value, so the token after them isn't mistaken
+/// for the command name.
+const VALUE_FLAGS: &[&str] = &[
+    "--config",
+    "--output",
+    "--sudo",
+    "--header",
+    "-H",
+    "--log-file",
+    "--request-timeout",
+];
+
+pub fn handle(config: &mut Config, command: AliasCommands) -> Result<()> {
+    match command {
+        AliasCommands::Set { name, expansion } => {
+            if is_builtin(&name) {
+                bail!("'{}' is a gitlab command and can't be an alias", name);
+            }
+            if name.starts_with('-') || name.contains(char::is_whitespace) {
+                bail!("Invalid alias name '{}'", name);
+            }
+            let expansion = expansion.join(" ");
+            config.aliases.insert(name.clone(), expansion.clone());
+            config.save()?;
+            success!("Alias {} = {}", name, expansion);
+        }
+        AliasCommands::List => {
+            if config.aliases.is_empty() {
+                println!("No aliases defined");
+            }
+            for (name, expansion) in &config.aliases {
+                println!("{} = {}", name, expansion);
+            }
+        }
+        AliasCommands::Remove { name } => {
+            if config.aliases.remove(&name).is_none() {
+                bail!("No alias named '{}'", name);
+            }
+            config.save()?;
+            success!("Removed alias {}", name);
+        }
+    }
+    Ok(())
+}
+
+/// The `--config` path given on the command line, needed to find aliases
+/// before clap has parsed anything.
+pub fn config_arg(args: &[String]) -> Option<std::path::PathBuf> {
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == "--config" {
+            args.get(i + 1).map(Into::into)
+        } else {
+            arg.strip_prefix("--config=").map(Into::into)
+        }
+    })
+}
+
+/// Replace a leading alias with its expansion, repeatedly, until the command
+/// is a built-in one. Built-in commands always win over aliases of the same name.
+pub fn expand(mut args: Vec<String>, aliases: &BTreeMap<String, String>) -> Result<Vec<String>> {
+    let mut chain: Vec<String> = Vec::new();
+    while let Some(pos) = command_position(&args) {
+        let name = &args[pos];
+        let Some(expansion) = aliases.get(name).filter(|_| !is_builtin(name)) else {
+            break;
+        };
+        if chain.contains(name) {
+            chain.push(name.clone());
+            bail!("Alias loop: {}", chain.join(" -> "));
+        }
+        if chain.len() >= MAX_ALIAS_DEPTH {
+            bail!(
+                "Alias {} expands more than {} levels deep",
+                chain[0],
+                MAX_ALIAS_DEPTH
+            );
+        }
+        chain.push(name.clone());
+        let words: Vec<String> = expansion.split_whitespace().map(String::from).collect();
+        args.splice(pos..=pos, words);
+    }
+    Ok(args)
+}
+
+/// Index of the first argument that isn't a global option or its value.
+fn command_position(args: &[String]) -> Option<usize> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            i += 2;
+        } else if arg.starts_with('-') {
+            i += 1;
+        } else {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn is_builtin(name: &str) -> bool {
+    Cli::command()
+        .get_subcommands()
+        .any(|cmd| cmd.get_name() == name || cmd.get_all_aliases().any(|a| a == name))
+}
@@ -1,16 +1,20 @@
 use anyhow::Result;
 
+use crate::api::ApiError;
 use crate::cli::BranchCommands;
-use crate::commands::print::print_protected_branches;
+use crate::commands::print::{print_output, print_protected_branches};
 use crate::config::Config;
 use crate::get_client;
 
 pub async fn handle(config: &mut Config, command: BranchCommands) -> Result<()> {
     match command {
         BranchCommands::List { project } => handle_list(config, project.as_deref()).await,
-        BranchCommands::Protect { branch, allow_force_push, project } => {
-            handle_protect(config, project.as_deref(), &branch, allow_force_push).await
-        }
+        BranchCommands::Protect {
+            branch,
+            allow_force_push,
+            project,
+            ..
+        } => handle_protect(config, project.as_deref(), &branch, allow_force_push).await,
         BranchCommands::Unprotect { branch, project } => {
             handle_unprotect(config, project.as_deref(), &branch).await
         }
@@ -20,7 +24,7 @@ pub async fn handle(config: &mut Config, command: BranchCommands) -> Result<()>
 async fn handle_list(config: &mut Config, project: Option<&str>) -> Result<()> {
     let client = get_client(config, project).await?;
     let result = client.list_protected_branches().await?;
-    print_protected_branches(&result);
+    print_output(&result, print_protected_branches);
     Ok(())
 }
 
@@ -31,14 +35,49 @@ async fn handle_protect(
     allow_force_push: bool,
 ) -> Result<()> {
     let client = get_client(config, project).await?;
-    client.protect_branch(branch, allow_force_push).await?;
-    println!("Protected branch: {}", branch);
+    match client.protect_branch(branch, allow_force_push).await {
+        Ok(result) => {
+            print_output(&result, |_| success!("Protected branch: {}", branch));
+            return Ok(());
+        }
+        Err(e)
+            if e.downcast_ref::<ApiError>()
+                .is_some_and(ApiError::is_conflict) => {}
+        Err(e) => return Err(e),
+    }
+
+    // Already protected: bring the existing rule in line with the flags
+    let current = client.get_protected_branch(branch).await?;
+    if current["allow_force_push"].as_bool() == Some(allow_force_push) {
+        print_output(&current, |_| {
+            success!("Branch {} is already protected with these settings", branch)
+        });
+        return Ok(());
+    }
+    let result = match client
+        .update_protected_branch(branch, allow_force_push)
+        .await
+    {
+        Ok(result) => result,
+        // GitLab before 15.6 has no PATCH endpoint; replace the rule instead
+        Err(e)
+            if e.downcast_ref::<ApiError>()
+                .is_some_and(ApiError::is_not_found) =>
+        {
+            client.unprotect_branch(branch).await?;
+            client.protect_branch(branch, allow_force_push).await?
+        }
+        Err(e) => return Err(e),
+    };
+    print_output(&result, |_| {
+        success!("Updated protection on branch: {}", branch)
+    });
     Ok(())
 }
 
 async fn handle_unprotect(config: &mut Config, project: Option<&str>, branch: &str) -> Result<()> {
     let client = get_client(config, project).await?;
     client.unprotect_branch(branch).await?;
-    println!("Unprotected branch: {}", branch);
+    success!("Unprotected branch: {}", branch);
     Ok(())
 }
@@ -1,46 +1,217 @@
 use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::io::Write;
+use std::time::{Duration, Instant};
 
+use crate::api::{Client, PipelineListParams, RequestTimeout};
 use crate::cli::{CiCommands, VarsCommands};
-use crate::commands::print::{print_ci_variables};
+use crate::commands::parse_date;
+use crate::commands::print::{
+    print_ci_lint, print_ci_variables, print_output, print_page, print_pipelines,
+};
 use crate::config::Config;
 use crate::get_client;
 
+const PIPELINE_TYPES: &[&str] = &["branch", "merge_request", "merge_train"];
+
+const PIPELINE_STATUSES: &[&str] = &[
+    "created",
+    "waiting_for_resource",
+    "preparing",
+    "pending",
+    "running",
+    "success",
+    "failed",
+    "canceled",
+    "skipped",
+    "manual",
+    "scheduled",
+];
+
 pub async fn handle(config: &mut Config, command: CiCommands) -> Result<()> {
     match command {
-        CiCommands::Status { id, branch, mr, project } => handle_status(config, project.as_deref(), id, branch, mr).await,
-        CiCommands::Wait { id, branch, interval, project } => handle_wait(config, project.as_deref(), id, branch, interval).await,
-        CiCommands::Logs { job, pipeline, branch, project } => handle_logs(config, project.as_deref(), job, pipeline, branch).await,
-        CiCommands::Retry { job, pipeline, branch, project } => handle_retry(config, project.as_deref(), job, pipeline, branch).await,
-        CiCommands::Vars { command, project } => handle_vars(config, project.as_deref(), command).await,
+        CiCommands::List {
+            branch,
+            status,
+            updated_after,
+            updated_before,
+            per_page,
+            project,
+        } => {
+            let updated_after = updated_after
+                .map(|d| parse_date("--updated-after", &d))
+                .transpose()?;
+            let updated_before = updated_before
+                .map(|d| parse_date("--updated-before", &d))
+                .transpose()?;
+            handle_list(
+                config,
+                project.as_deref(),
+                PipelineListParams {
+                    per_page,
+                    ref_name: branch,
+                    status,
+                    updated_after,
+                    updated_before,
+                },
+            )
+            .await
+        }
+        CiCommands::Status {
+            id,
+            branch,
+            mr,
+            pipeline_type,
+            only,
+            name,
+            project,
+        } => {
+            let filter = JobFilter { status: only, name };
+            handle_status(
+                config,
+                project.as_deref(),
+                id,
+                branch,
+                mr,
+                pipeline_type.as_deref(),
+                &filter,
+            )
+            .await
+        }
+        CiCommands::Wait {
+            id,
+            branch,
+            interval,
+            timeout,
+            project,
+        } => handle_wait(config, project.as_deref(), id, branch, interval, timeout).await,
+        CiCommands::Logs {
+            job,
+            pipeline,
+            branch,
+            project,
+        } => handle_logs(config, project.as_deref(), job, pipeline, branch).await,
+        CiCommands::Retry {
+            job,
+            pipeline: true,
+            failed_only: true,
+            project,
+            ..
+        } => handle_retry_failed(config, project.as_deref(), &job).await,
+        CiCommands::Retry {
+            job,
+            pipeline,
+            branch,
+            project,
+            ..
+        } => handle_retry(config, project.as_deref(), job, pipeline, branch).await,
+        CiCommands::Lint { file, project } => handle_lint(config, project.as_deref(), &file).await,
+        CiCommands::Vars {
+            command,
+            env,
+            project,
+        } => handle_vars(config, project.as_deref(), env.as_deref(), command).await,
+    }
+}
+
+async fn handle_lint(
+    config: &mut Config,
+    project: Option<&str>,
+    file: &std::path::Path,
+) -> Result<()> {
+    let content = if file.as_os_str() == "-" {
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf)?;
+        buf
+    } else {
+        std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?
+    };
+    let client = get_client(config, project).await?;
+    let result = client.lint_ci_config(&content).await?;
+    print_output(&result, print_ci_lint);
+    if !result["valid"].as_bool().unwrap_or(false) {
+        let errors = result["errors"].as_array().map_or(0, Vec::len);
+        bail!("CI config is invalid ({} error(s))", errors);
     }
+    Ok(())
+}
+
+async fn handle_list(
+    config: &mut Config,
+    project: Option<&str>,
+    params: PipelineListParams,
+) -> Result<()> {
+    if let Some(status) = params.status.as_deref() {
+        if !PIPELINE_STATUSES.contains(&status) {
+            bail!(
+                "Invalid status: '{}' (expected: {})",
+                status,
+                PIPELINE_STATUSES.join(", ")
+            );
+        }
+    }
+    let client = get_client(config, project).await?;
+    let (result, headers) = client.list_pipelines(&params).await?;
+    print_page(&result, &headers, print_pipelines);
+    Ok(())
 }
 
 async fn handle_vars(
     config: &mut Config,
     project: Option<&str>,
+    env: Option<&str>,
     command: Option<VarsCommands>,
 ) -> Result<()> {
     match command {
-        None => handle_vars_list(config, project).await,
-        Some(VarsCommands::Get { key, project: var_project }) => {
+        None => handle_vars_list(config, project, env).await,
+        Some(VarsCommands::Get {
+            key,
+            env,
+            decode,
+            project: var_project,
+        }) => {
             let effective_project = var_project.as_deref().or(project);
-            handle_vars_get(config, effective_project, &key).await
+            handle_vars_get(config, effective_project, &key, env.as_deref(), decode).await
         }
     }
 }
 
-async fn handle_vars_list(config: &mut Config, project: Option<&str>) -> Result<()> {
+async fn handle_vars_list(
+    config: &mut Config,
+    project: Option<&str>,
+    env: Option<&str>,
+) -> Result<()> {
     let client = get_client(config, project).await?;
-    let vars = client.list_ci_variables().await?;
-    print_ci_variables(&vars);
+    let mut vars = client.list_ci_variables().await?;
+    if let (Some(pattern), Some(list)) = (env, vars.as_array_mut()) {
+        list.retain(|var| glob_match(pattern, var["environment_scope"].as_str().unwrap_or("*")));
+    }
+    print_output(&vars, print_ci_variables);
     Ok(())
 }
 
-async fn handle_vars_get(config: &mut Config, project: Option<&str>, key: &str) -> Result<()> {
+async fn handle_vars_get(
+    config: &mut Config,
+    project: Option<&str>,
+    key: &str,
+    env: Option<&str>,
+    decode: bool,
+) -> Result<()> {
     let client = get_client(config, project).await?;
-    let var = client.get_ci_variable(key).await?;
+    let var = client.get_ci_variable(key, env).await?;
     let value = var["value"].as_str().unwrap_or("");
-    print!("{}", value);
+    let is_file = var["variable_type"].as_str() == Some("file");
+    let decoded = if decode && is_file {
+        // File variables are usually stored verbatim; only decode when the
+        // content is valid base64, otherwise fall back to the raw value.
+        STANDARD.decode(value.trim()).ok()
+    } else {
+        None
+    };
+    let mut stdout = std::io::stdout();
+    stdout.write_all(decoded.as_deref().unwrap_or(value.as_bytes()))?;
+    stdout.flush()?;
     Ok(())
 }
 
@@ -50,7 +221,19 @@ async fn handle_status(
     id: Option<u64>,
     branch: Option<String>,
     mr: Option<u64>,
+    pipeline_type: Option<&str>,
+    filter: &JobFilter,
 ) -> Result<()> {
+    filter.validate()?;
+    if let Some(kind) = pipeline_type {
+        if !PIPELINE_TYPES.contains(&kind) {
+            bail!(
+                "Invalid type: '{}' (expected: {})",
+                kind,
+                PIPELINE_TYPES.join(", ")
+            );
+        }
+    }
     let client = get_client(config, project).await?;
     let pipeline = if let Some(pid) = id {
         client.get_pipeline(pid).await?
@@ -59,10 +242,14 @@ async fn handle_status(
         let arr = pipelines
             .as_array()
             .ok_or_else(|| anyhow::anyhow!("No pipelines found for MR !{}", mr_iid))?;
-        if arr.is_empty() {
-            bail!("No pipelines found for MR !{}", mr_iid);
+        let found = arr
+            .iter()
+            .find(|p| pipeline_type.is_none_or(|kind| mr_pipeline_type(p) == kind));
+        match (found, pipeline_type) {
+            (Some(p), _) => p.clone(),
+            (None, Some(kind)) => bail!("No {} pipelines found for MR !{}", kind, mr_iid),
+            (None, None) => bail!("No pipelines found for MR !{}", mr_iid),
         }
-        arr[0].clone()
     } else {
         let ref_name = detect_branch(branch)?;
         find_latest_pipeline(&client, &ref_name).await?
@@ -71,16 +258,21 @@ async fn handle_status(
     let pipeline_id = pipeline["id"].as_u64().unwrap();
     let jobs = client.list_pipeline_jobs(pipeline_id).await?;
 
+    let source = pipeline["source"]
+        .as_str()
+        .map(|s| format!(" [{}]", s))
+        .unwrap_or_default();
     println!(
-        "Pipeline #{} - {} ({})",
+        "Pipeline #{} - {} ({}){}",
         pipeline["id"],
         pipeline["status"].as_str().unwrap_or("unknown"),
-        pipeline["ref"].as_str().unwrap_or("")
+        pipeline["ref"].as_str().unwrap_or(""),
+        source
     );
     println!();
 
     if let Some(jobs_arr) = jobs.as_array() {
-        for job in jobs_arr {
+        for job in jobs_arr.iter().filter(|j| filter.matches(j)) {
             println!(
                 "  {} - {} ({})",
                 job["name"].as_str().unwrap_or("?"),
@@ -88,71 +280,216 @@ async fn handle_status(
                 job["stage"].as_str().unwrap_or("?")
             );
         }
+        if !jobs_arr.is_empty() {
+            println!();
+            println!("Jobs: {}", job_status_summary(jobs_arr));
+        }
     }
     Ok(())
 }
 
+/// Classify an MR pipeline by its ref: `refs/merge-requests/<iid>/train` runs
+/// on a merge train, `/head` and `/merge` (merged results) are merge request
+/// pipelines, and anything else ran on the source branch.
+fn mr_pipeline_type(pipeline: &serde_json::Value) -> &'static str {
+    let ref_name = pipeline["ref"].as_str().unwrap_or("");
+    match ref_name.strip_prefix("refs/merge-requests/") {
+        Some(rest) if rest.ends_with("/train") => "merge_train",
+        Some(_) => "merge_request",
+        None => "branch",
+    }
+}
+
+/// Count jobs per status across the whole pipeline, e.g. `12 success, 2 failed`.
+fn job_status_summary(jobs: &[serde_json::Value]) -> String {
+    let count = |status: &str| {
+        jobs.iter()
+            .filter(|j| j["status"].as_str() == Some(status))
+            .count()
+    };
+    JOB_STATUSES
+        .iter()
+        .map(|&status| (status, count(status)))
+        .filter(|&(_, n)| n > 0)
+        .map(|(status, n)| format!("{} {}", n, status))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Job filters for `ci status`; the pipeline header is always shown.
+struct JobFilter {
+    status: Option<String>,
+    name: Option<String>,
+}
+
+const JOB_STATUSES: &[&str] = &[
+    "created",
+    "pending",
+    "running",
+    "success",
+    "failed",
+    "canceled",
+    "skipped",
+    "manual",
+    "scheduled",
+    "waiting_for_resource",
+    "preparing",
+];
+
+impl JobFilter {
+    fn validate(&self) -> Result<()> {
+        if let Some(status) = &self.status {
+            if !JOB_STATUSES.contains(&status.as_str()) {
+                bail!(
+                    "Invalid job status: '{}' (expected one of: {})",
+                    status,
+                    JOB_STATUSES.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn matches(&self, job: &serde_json::Value) -> bool {
+        let status_ok = self
+            .status
+            .as_deref()
+            .is_none_or(|s| job["status"].as_str() == Some(s));
+        let name_ok = self
+            .name
+            .as_deref()
+            .is_none_or(|pattern| glob_match(pattern, job["name"].as_str().unwrap_or("")));
+        status_ok && name_ok
+    }
+}
+
+/// Minimal glob matching supporting `*` (any run) and `?` (any single char).
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star_pi, star_ti)) = backtrack {
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            backtrack = Some((star_pi, star_ti + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
 async fn handle_wait(
     config: &mut Config,
     project: Option<&str>,
     id: Option<u64>,
     branch: Option<String>,
     interval: u64,
+    timeout: Option<u64>,
 ) -> Result<()> {
     let client = get_client(config, project).await?;
-
     let ref_name = if id.is_none() {
         Some(detect_branch(branch)?)
     } else {
         branch
     };
+    let timeout = timeout.map(Duration::from_secs);
+    wait_for_pipeline(
+        &client,
+        id,
+        ref_name.as_deref(),
+        Duration::from_secs(interval),
+        timeout,
+    )
+    .await
+}
 
-    loop {
-        let pipeline = if let Some(pid) = id {
-            client.get_pipeline(pid).await?
-        } else {
-            let pipelines = client
-                .list_pipelines_for_branch(ref_name.as_deref(), 1)
-                .await?;
-            let arr = pipelines.as_array().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No pipelines found for branch {}",
-                    ref_name.as_deref().unwrap_or("?")
-                )
-            })?;
-            if arr.is_empty() {
-                bail!(
-                    "No pipelines found for branch {}",
-                    ref_name.as_deref().unwrap_or("?")
-                );
-            }
-            arr[0].clone()
-        };
+/// Poll a pipeline (or the latest one on `ref_name`) until it finishes.
+/// `timeout` covers the whole wait; `--request-timeout` only limits each poll,
+/// and a poll that hits it is retried at the next interval.
+async fn wait_for_pipeline(
+    client: &Client,
+    id: Option<u64>,
+    ref_name: Option<&str>,
+    interval: Duration,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let deadline = timeout.map(|limit| Instant::now() + limit);
+    let mut last_seen = None;
 
-        let status = pipeline["status"].as_str().unwrap_or("unknown");
-        let pipeline_ref = pipeline["ref"].as_str().unwrap_or("");
-        let pipeline_id = pipeline["id"].as_u64().unwrap();
+    loop {
+        match fetch_pipeline(client, id, ref_name).await {
+            Ok(pipeline) => {
+                let status = pipeline["status"].as_str().unwrap_or("unknown");
+                let pipeline_ref = pipeline["ref"].as_str().unwrap_or("");
+                let pipeline_id = pipeline["id"].as_u64().unwrap();
 
-        eprintln!("Pipeline #{} - {} ({})", pipeline_id, status, pipeline_ref);
+                eprintln!("Pipeline #{} - {} ({})", pipeline_id, status, pipeline_ref);
 
-        match status {
-            "success" => {
-                println!("Pipeline #{} succeeded", pipeline_id);
-                break;
+                match status {
+                    "success" => {
+                        success!("Pipeline #{} succeeded", pipeline_id);
+                        return Ok(());
+                    }
+                    "failed" | "canceled" | "skipped" => {
+                        bail!("Pipeline #{} {}", pipeline_id, status);
+                    }
+                    "running"
+                    | "pending"
+                    | "created"
+                    | "waiting_for_resource"
+                    | "preparing"
+                    | "scheduled" => {
+                        last_seen = Some(format!("pipeline #{} (still {})", pipeline_id, status));
+                    }
+                    _ => {
+                        bail!("Unknown pipeline status: {}", status);
+                    }
+                }
             }
-            "failed" | "canceled" | "skipped" => {
-                bail!("Pipeline #{} {}", pipeline_id, status);
+            Err(e) if e.downcast_ref::<RequestTimeout>().is_some() => {
+                eprintln!("Warning: {}; retrying", e);
             }
-            "running" | "pending" | "created" | "waiting_for_resource" | "preparing"
-            | "scheduled" => {
-                tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
-            }
-            _ => {
-                bail!("Unknown pipeline status: {}", status);
+            Err(e) => return Err(e),
+        }
+
+        let mut pause = interval;
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                bail!(
+                    "Timed out after {}s waiting for {}",
+                    timeout.unwrap_or_default().as_secs(),
+                    last_seen.as_deref().unwrap_or("the pipeline")
+                );
             }
+            pause = pause.min(remaining);
         }
+        tokio::time::sleep(pause).await;
+    }
+}
+
+async fn fetch_pipeline(
+    client: &Client,
+    id: Option<u64>,
+    ref_name: Option<&str>,
+) -> Result<serde_json::Value> {
+    if let Some(pid) = id {
+        return client.get_pipeline(pid).await;
+    }
+    let pipelines = client.list_pipelines_for_branch(ref_name, 1).await?;
+    match pipelines.as_array().and_then(|arr| arr.first()) {
+        Some(pipeline) => Ok(pipeline.clone()),
+        None => bail!("No pipelines found for branch {}", ref_name.unwrap_or("?")),
     }
-    Ok(())
 }
 
 async fn handle_logs(
@@ -191,7 +528,7 @@ async fn handle_retry(
         let result = client.retry_pipeline(pipeline_id).await?;
         let new_pipeline_id = result["id"].as_u64().unwrap_or(pipeline_id);
         let web_url = result["web_url"].as_str().unwrap_or("");
-        println!("Pipeline #{} retried", new_pipeline_id);
+        success!("Pipeline #{} retried", new_pipeline_id);
         if !web_url.is_empty() {
             println!("{}", web_url);
         }
@@ -201,7 +538,7 @@ async fn handle_retry(
         let job_name = result["name"].as_str().unwrap_or("unknown");
         let new_job_id = result["id"].as_u64().unwrap_or(job_id);
         let web_url = result["web_url"].as_str().unwrap_or("");
-        println!("Job '{}' (#{}) retried", job_name, new_job_id);
+        success!("Job '{}' (#{}) retried", job_name, new_job_id);
         if !web_url.is_empty() {
             println!("{}", web_url);
         }
@@ -209,6 +546,58 @@ async fn handle_retry(
     Ok(())
 }
 
+async fn handle_retry_failed(
+    config: &mut Config,
+    project: Option<&str>,
+    pipeline: &str,
+) -> Result<()> {
+    let pipeline_id: u64 = pipeline.parse().context("Pipeline ID must be numeric")?;
+    let client = get_client(config, project).await?;
+    let jobs = client.list_pipeline_jobs(pipeline_id).await?;
+    let failed: Vec<&serde_json::Value> = jobs
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter(|j| j["status"].as_str() == Some("failed"))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if failed.is_empty() {
+        success!("No failed jobs in pipeline #{}", pipeline_id);
+        return Ok(());
+    }
+
+    let mut errors = 0;
+    for job in &failed {
+        let job_id = job["id"].as_u64().unwrap_or(0);
+        let job_name = job["name"].as_str().unwrap_or("unknown");
+        match client.retry_job(job_id).await {
+            Ok(result) => {
+                let new_job_id = result["id"].as_u64().unwrap_or(job_id);
+                success!(
+                    "Job '{}' (#{}) retried as #{}",
+                    job_name,
+                    job_id,
+                    new_job_id
+                );
+            }
+            Err(e) => {
+                eprintln!("Job '{}' (#{}): {}", job_name, job_id, e);
+                errors += 1;
+            }
+        }
+    }
+    if errors > 0 {
+        bail!(
+            "{} of {} failed jobs could not be retried",
+            errors,
+            failed.len()
+        );
+    }
+    Ok(())
+}
+
 fn detect_branch(branch: Option<String>) -> Result<String> {
     if let Some(b) = branch {
         return Ok(b);
@@ -237,21 +626,14 @@ async fn find_latest_pipeline(
     Ok(arr[0].clone())
 }
 
-async fn find_latest_pipeline_id(
-    client: &crate::api::Client,
-    ref_name: &str,
-) -> Result<u64> {
+async fn find_latest_pipeline_id(client: &crate::api::Client, ref_name: &str) -> Result<u64> {
     let pipeline = find_latest_pipeline(client, ref_name).await?;
     pipeline["id"]
         .as_u64()
         .ok_or_else(|| anyhow::anyhow!("Invalid pipeline ID"))
 }
 
-async fn resolve_job_id(
-    client: &crate::api::Client,
-    job: &str,
-    pipeline_id: u64,
-) -> Result<u64> {
+async fn resolve_job_id(client: &crate::api::Client, job: &str, pipeline_id: u64) -> Result<u64> {
     if let Ok(id) = job.parse::<u64>() {
         return Ok(id);
     }
@@ -278,3 +660,82 @@ async fn resolve_job_id_from_branch(
     let pipeline_id = find_latest_pipeline_id(client, &ref_name).await?;
     resolve_job_id(client, job, pipeline_id).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::{MockResponse, MockServer};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn glob_match_star_positions() {
+        assert!(glob_match("*-test", "unit-test"));
+        assert!(glob_match("build-*-linux", "build-arm64-linux"));
+        assert!(glob_match("deploy*", "deploy-prod"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(glob_match("test ?", "test 3"));
+    }
+
+    #[test]
+    fn glob_match_rejects_non_matches() {
+        assert!(!glob_match("*-test", "unit-tests"));
+        assert!(!glob_match("build-*-linux", "build-arm64-macos"));
+        assert!(!glob_match("deploy*", "predeploy"));
+        assert!(!glob_match("lint", "lint-fix"));
+        assert!(!glob_match("test ?", "test 10"));
+    }
+
+    fn pipeline(status: &str) -> MockResponse {
+        MockResponse::json(format!(r#"{{"id":5,"ref":"main","status":"{}"}}"#, status))
+    }
+
+    /// A poll slower than `--request-timeout` is retried; it doesn't end the wait.
+    #[tokio::test]
+    async fn wait_retries_a_timed_out_poll() {
+        let polls = AtomicUsize::new(0);
+        let server = MockServer::start(move |_| match polls.fetch_add(1, Ordering::SeqCst) {
+            0 => pipeline("running"),
+            1 => pipeline("running").delay(Duration::from_millis(500)),
+            _ => pipeline("success"),
+        })
+        .await;
+        let client = Client::new(&server.url(), "token", "group/project")
+            .unwrap()
+            .with_timeout(Some(Duration::from_millis(100)));
+
+        wait_for_pipeline(
+            &client,
+            Some(5),
+            None,
+            Duration::from_millis(10),
+            Some(Duration::from_secs(5)),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn wait_stops_at_the_deadline() {
+        let server = MockServer::start(|_| pipeline("running")).await;
+        let client = Client::new(&server.url(), "token", "group/project").unwrap();
+
+        let started = Instant::now();
+        let err = wait_for_pipeline(
+            &client,
+            Some(5),
+            None,
+            Duration::from_millis(20),
+            Some(Duration::from_millis(200)),
+        )
+        .await
+        .unwrap_err();
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(
+            err.to_string().contains("pipeline #5 (still running)"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::api::{ApiError, CommitListParams};
+use crate::cli::CommitCommands;
+use crate::commands::parse_date;
+use crate::commands::print::{print_commit_statuses, print_commits, print_output, print_page};
+use crate::config::Config;
+use crate::get_client;
+
+const STATUS_STATES: &[&str] = &["pending", "running", "success", "failed", "canceled"];
+
+pub async fn handle(config: &mut Config, command: CommitCommands) -> Result<()> {
+    match command {
+        CommitCommands::List {
+            ref_name,
+            author,
+            since,
+            until,
+            path,
+            per_page,
+            project,
+        } => {
+            let since = since.map(|d| parse_date("--since", &d)).transpose()?;
+            let until = until.map(|d| parse_date("--until", &d)).transpose()?;
+            handle_list(
+                config,
+                project.as_deref(),
+                CommitListParams {
+                    per_page,
+                    ref_name,
+                    author,
+                    since,
+                    until,
+                    path,
+                },
+            )
+            .await
+        }
+        CommitCommands::CherryPick {
+            sha,
+            branch,
+            project,
+            ..
+        } => handle_cherry_pick(config, project.as_deref(), &sha, &branch).await,
+        CommitCommands::Revert {
+            sha,
+            branch,
+            project,
+            ..
+        } => handle_revert(config, project.as_deref(), &sha, &branch).await,
+        CommitCommands::Status {
+            sha,
+            state: Some(state),
+            name,
+            target_url,
+            description,
+            project,
+        } => {
+            handle_set_status(
+                config,
+                project.as_deref(),
+                &sha,
+                &state,
+                name.as_deref(),
+                target_url.as_deref(),
+                description.as_deref(),
+            )
+            .await
+        }
+        CommitCommands::Status {
+            sha,
+            state: None,
+            project,
+            ..
+        } => handle_statuses(config, project.as_deref(), &sha).await,
+    }
+}
+
+async fn handle_list(
+    config: &mut Config,
+    project: Option<&str>,
+    params: CommitListParams,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let (result, headers) = client.list_commits(&params).await?;
+    print_page(&result, &headers, print_commits);
+    Ok(())
+}
+
+async fn handle_cherry_pick(
+    config: &mut Config,
+    project: Option<&str>,
+    sha: &str,
+    branch: &str,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client
+        .cherry_pick_commit(sha, branch)
+        .await
+        .map_err(conflict("Cherry-pick", sha, branch))?;
+    print_output(&result, |commit| {
+        let new_sha = commit["id"].as_str().unwrap_or("?");
+        let title = commit["title"].as_str().unwrap_or("");
+        success!(
+            "Cherry-picked {} onto {} as {}: {}",
+            sha,
+            branch,
+            new_sha,
+            title
+        );
+    });
+    Ok(())
+}
+
+async fn handle_revert(
+    config: &mut Config,
+    project: Option<&str>,
+    sha: &str,
+    branch: &str,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client
+        .revert_commit(sha, branch)
+        .await
+        .map_err(conflict("Revert", sha, branch))?;
+    print_output(&result, |commit| {
+        let new_sha = commit["id"].as_str().unwrap_or("?");
+        let title = commit["title"].as_str().unwrap_or("");
+        success!("Reverted {} on {} as {}: {}", sha, branch, new_sha, title);
+    });
+    Ok(())
+}
+
+/// Replace a 409 from cherry-pick/revert with a message saying the change
+/// doesn't apply cleanly; other errors pass through unchanged.
+fn conflict<'a>(
+    action: &'a str,
+    sha: &'a str,
+    branch: &'a str,
+) -> impl FnOnce(anyhow::Error) -> anyhow::Error + 'a {
+    move |err| match err.downcast_ref::<ApiError>() {
+        Some(api_err) if api_err.is_conflict() => anyhow!(
+            "{} of {} on {} conflicts; resolve it locally: {}",
+            action,
+            sha,
+            branch,
+            api_err.body
+        ),
+        _ => err,
+    }
+}
+
+async fn handle_statuses(config: &mut Config, project: Option<&str>, sha: &str) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client.get_commit_statuses(sha).await?;
+    print_output(&result, print_commit_statuses);
+    Ok(())
+}
+
+async fn handle_set_status(
+    config: &mut Config,
+    project: Option<&str>,
+    sha: &str,
+    state: &str,
+    name: Option<&str>,
+    target_url: Option<&str>,
+    description: Option<&str>,
+) -> Result<()> {
+    if !STATUS_STATES.contains(&state) {
+        bail!(
+            "Invalid status state: '{}' (expected one of: {})",
+            state,
+            STATUS_STATES.join(", ")
+        );
+    }
+    let client = get_client(config, project).await?;
+    let result = client
+        .set_commit_status(sha, state, name, target_url, description)
+        .await?;
+    let name = result["name"].as_str().unwrap_or("default");
+    success!("Set status {} = {} on {}", name, state, sha);
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anyhow::{bail, Result};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::{get_client, get_group_client};
+
+struct Report {
+    failures: usize,
+}
+
+impl Report {
+    fn pass(&self, check: &str, detail: &str) {
+        println!("[ok]   {}: {}", check, detail);
+    }
+
+    fn fail(&mut self, check: &str, detail: &str, hint: &str) {
+        self.failures += 1;
+        println!("[FAIL] {}: {}", check, detail);
+        println!("       hint: {}", hint);
+    }
+}
+
+pub async fn run(config_path: Option<&Path>) -> Result<()> {
+    let mut report = Report { failures: 0 };
+
+    let mut config = match Config::load(config_path) {
+        Ok(config) => {
+            if config.path().exists() {
+                report.pass("config", &config.path().display().to_string());
+            } else if config.token.is_some() {
+                report.pass("config", "no config file, using environment");
+            } else {
+                report.fail(
+                    "config",
+                    &format!("{} not found", config.path().display()),
+                    "Run: gitlab auth login, or gitlab config --host <url> --project <project>",
+                );
+            }
+            config
+        }
+        Err(e) => {
+            report.fail(
+                "config",
+                &format!("{:#}", e),
+                "Fix or remove the config file, then run: gitlab auth login",
+            );
+            bail!("1 check failed");
+        }
+    };
+
+    check_host(&mut report, config.host()).await;
+    if check_token(&mut report, &config) {
+        check_user(&mut report, &mut config).await;
+        check_project(&mut report, &mut config).await;
+    }
+
+    if report.failures > 0 {
+        bail!("{} check(s) failed", report.failures);
+    }
+    println!("All checks passed");
+    Ok(())
+}
+
+async fn check_host(report: &mut Report, host: &str) {
+    match reqwest::get(host).await {
+        Ok(_) => report.pass("host", host),
+        Err(e) => report.fail(
+            "host",
+            &format!("{} unreachable: {}", host, e),
+            "Check the URL and network, or run: gitlab config --host <url>",
+        ),
+    }
+}
+
+fn check_token(report: &mut Report, config: &Config) -> bool {
+    if let Some(oauth2) = &config.oauth2 {
+        if oauth2.is_expired() {
+            // Expired OAuth2 tokens are refreshed on use, so only warn here
+            report.pass(
+                "token",
+                "OAuth2 token expired, will refresh on next request",
+            );
+        } else {
+            report.pass("token", &format!("OAuth2, expires {}", oauth2.expires_at));
+        }
+        true
+    } else if config.token.is_some() {
+        report.pass("token", "static token");
+        true
+    } else {
+        report.fail(
+            "token",
+            "no token configured",
+            "Run: gitlab auth login, or set GITLAB_TOKEN",
+        );
+        false
+    }
+}
+
+async fn check_user(report: &mut Report, config: &mut Config) {
+    let result = match get_group_client(config).await {
+        Ok(client) => client.get_current_user().await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(user) => report.pass(
+            "user",
+            &format!("@{}", user["username"].as_str().unwrap_or("?")),
+        ),
+        Err(e) => report.fail(
+            "user",
+            &e.to_string(),
+            "The token may be revoked or lack read_user scope. Run: gitlab auth login",
+        ),
+    }
+}
+
+async fn check_project(report: &mut Report, config: &mut Config) {
+    let Some(project) = config.project.clone() else {
+        report.fail(
+            "project",
+            "no default project configured",
+            "Run: gitlab config --project <group/project>, or pass --project",
+        );
+        return;
+    };
+    let result = match get_client(config, Some(&project)).await {
+        Ok(client) => client.get_project().await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(_) => report.pass("project", &project),
+        Err(e) => report.fail(
+            "project",
+            &format!("{}: {}", project, e),
+            "Check the project path and your access to it",
+        ),
+    }
+}
@@ -1,15 +1,26 @@
 use anyhow::Result;
+use std::collections::HashSet;
 
 use crate::cli::GroupCommands;
-use crate::commands::print::{print_group_members, print_subgroups};
+use crate::commands::not_found;
+use crate::commands::print::{
+    print_group_detail, print_group_members, print_output, print_subgroups,
+};
 use crate::config::Config;
 use crate::get_group_client;
 
 pub async fn handle(config: &mut Config, command: GroupCommands) -> Result<()> {
     match command {
-        GroupCommands::Members { group, per_page, email } => handle_members(config, &group, per_page, email).await,
-        GroupCommands::Subgroups { group, per_page } => handle_subgroups(config, &group, per_page).await,
-        GroupCommands::Show { group } => handle_show(config, &group).await,
+        GroupCommands::Members {
+            group,
+            per_page,
+            email,
+            all_levels,
+        } => handle_members(config, &group, per_page, email, all_levels).await,
+        GroupCommands::Subgroups { group, per_page } => {
+            handle_subgroups(config, &group, per_page).await
+        }
+        GroupCommands::Show { group, json } => handle_show(config, &group, json).await,
     }
 }
 
@@ -18,23 +29,63 @@ async fn handle_members(
     group: &str,
     per_page: u32,
     email: bool,
+    all_levels: bool,
 ) -> Result<()> {
     let client = get_group_client(config).await?;
-    let result = client.list_group_members(group, per_page, email).await?;
-    print_group_members(&result, email);
+    if !all_levels {
+        let result = client.list_group_members(group, per_page, email).await?;
+        print_output(&result, |v| print_group_members(v, email, false));
+        return Ok(());
+    }
+
+    let mut result = client.list_all_group_members(group, per_page).await?;
+    let direct = client.list_direct_group_members(group).await?;
+    mark_inherited_members(&mut result, &direct);
+    print_output(&result, |v| print_group_members(v, false, true));
     Ok(())
 }
 
+/// `/members/all` doesn't say where access comes from, so flag every member
+/// that isn't a direct member of the group as inherited.
+fn mark_inherited_members(all: &mut serde_json::Value, direct: &serde_json::Value) {
+    let direct_ids: HashSet<u64> = direct
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|m| m["id"].as_u64()).collect())
+        .unwrap_or_default();
+    if let Some(members) = all.as_array_mut() {
+        for member in members {
+            let id = member["id"].as_u64().unwrap_or(0);
+            member["inherited"] = serde_json::Value::Bool(!direct_ids.contains(&id));
+        }
+    }
+}
+
 async fn handle_subgroups(config: &mut Config, group: &str, per_page: u32) -> Result<()> {
     let client = get_group_client(config).await?;
     let result = client.list_group_subgroups(group, per_page).await?;
-    print_subgroups(&result);
+    print_output(&result, print_subgroups);
     Ok(())
 }
 
-async fn handle_show(config: &mut Config, group: &str) -> Result<()> {
+async fn handle_show(config: &mut Config, group: &str, json: bool) -> Result<()> {
     let client = get_group_client(config).await?;
-    let result = client.get_group(group).await?;
-    println!("{}", serde_json::to_string_pretty(&result)?);
+    let mut result = client
+        .get_group(group)
+        .await
+        .map_err(not_found(|| format!("Group {} not found", group)))?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    let (projects, members, subgroups) = tokio::try_join!(
+        client.count_group_resource(group, "projects"),
+        client.count_group_resource(group, "members"),
+        client.count_group_resource(group, "subgroups"),
+    )?;
+    result["project_count"] = projects.into();
+    result["member_count"] = members.into();
+    result["subgroup_count"] = subgroups.into();
+    print_output(&result, print_group_detail);
     Ok(())
 }
@@ -1,61 +1,411 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
-use crate::api::IssueListParams;
+use crate::api::{IssueListParams, TimeTarget};
 use crate::cli::IssueCommands;
-use crate::commands::print::print_issues;
+use crate::commands::print::{
+    print_discussion_thread, print_issue_board, print_issue_detail, print_issue_links,
+    print_issues, print_output, print_page, text_output,
+};
+use crate::commands::template::{print_templates, resolve_description, TemplateKind};
+use crate::commands::{
+    changed, check_choice, check_day, not_found, resolve_optional_user, time, SORT_DIRECTIONS,
+};
 use crate::config::Config;
-use crate::get_client;
+use crate::{get_client, get_group_client};
 
 pub async fn handle(config: &mut Config, command: IssueCommands) -> Result<()> {
     match command {
-        IssueCommands::List { state, author, assignee, labels, search, created_after, per_page, project } => {
-            handle_list(config, project.as_deref(), IssueListParams { per_page, state, author_username: author, assignee_username: assignee, labels, search, created_after }).await
+        IssueCommands::List {
+            state,
+            author,
+            assignee,
+            labels,
+            search,
+            created_after,
+            weight,
+            due,
+            order_by,
+            sort,
+            per_page,
+            group,
+            project,
+        } => {
+            let weight = weight.map(|w| weight_filter(&w)).transpose()?;
+            let due_date = due.map(|d| due_filter(&d)).transpose()?;
+            handle_list(
+                config,
+                project.as_deref(),
+                group.as_deref(),
+                IssueListParams {
+                    per_page,
+                    state,
+                    author_username: author,
+                    assignee_username: assignee,
+                    labels,
+                    search,
+                    created_after,
+                    weight,
+                    due_date,
+                    order_by,
+                    sort,
+                },
+            )
+            .await
+        }
+        IssueCommands::Board {
+            group_by,
+            state,
+            labels,
+            assignee,
+            group,
+            project,
+        } => {
+            let params = IssueListParams {
+                per_page: 100,
+                state,
+                assignee_username: assignee,
+                labels,
+                ..Default::default()
+            };
+            handle_board(
+                config,
+                project.as_deref(),
+                group.as_deref(),
+                &group_by,
+                params,
+            )
+            .await
         }
         IssueCommands::Show { iid, project } => handle_show(config, project.as_deref(), iid).await,
-        IssueCommands::Create { title, description, labels, assignee, project } => {
-            handle_create(config, project.as_deref(), title, description, labels, assignee).await
+        IssueCommands::Create {
+            list_templates: true,
+            project,
+            ..
+        } => {
+            let client = get_client(config, project.as_deref()).await?;
+            print_templates(&client, TemplateKind::Issue).await
+        }
+        IssueCommands::Create {
+            title,
+            description,
+            template,
+            edit,
+            labels,
+            assignee,
+            weight,
+            due_date,
+            project,
+            ..
+        } => {
+            let title = title.context("--title is required")?;
+            if let Some(due) = &due_date {
+                check_day("--due-date", due)?;
+            }
+            handle_create(
+                config,
+                project.as_deref(),
+                title,
+                description,
+                template,
+                edit,
+                labels,
+                assignee,
+                weight,
+                due_date,
+            )
+            .await
+        }
+        IssueCommands::Update {
+            iid,
+            weight,
+            due_date,
+            project,
+            ..
+        } => handle_update(config, project.as_deref(), iid, weight, due_date).await,
+        IssueCommands::Link {
+            iid,
+            to,
+            link_type,
+            to_project,
+            project,
+        } => {
+            handle_link(
+                config,
+                project.as_deref(),
+                iid,
+                to,
+                &link_type,
+                to_project.as_deref(),
+            )
+            .await
+        }
+        IssueCommands::Links { iid, project } => {
+            handle_links(config, project.as_deref(), iid).await
+        }
+        IssueCommands::Subscribe { iid, project } => {
+            handle_subscribe(config, project.as_deref(), iid, true).await
         }
+        IssueCommands::Unsubscribe { iid, project } => {
+            handle_subscribe(config, project.as_deref(), iid, false).await
+        }
+        IssueCommands::Time { command } => time::handle(config, TimeTarget::Issue, command).await,
+        IssueCommands::Discussions {
+            iid,
+            per_page,
+            project,
+        } => handle_discussions(config, project.as_deref(), iid, per_page).await,
     }
 }
 
 async fn handle_list(
     config: &mut Config,
     project: Option<&str>,
+    group: Option<&str>,
     params: IssueListParams,
 ) -> Result<()> {
-    let client = get_client(config, project).await?;
-    let result = client.list_issues(&params).await?;
-    print_issues(&result);
+    check_choice("state", &params.state, &["opened", "closed", "all"])?;
+    if let Some(order) = &params.order_by {
+        check_choice(
+            "order_by",
+            order,
+            &["created_at", "updated_at", "priority", "due_date"],
+        )?;
+    }
+    if let Some(sort) = &params.sort {
+        check_choice("sort", sort, SORT_DIRECTIONS)?;
+    }
+    let (result, headers) = if let Some(group) = group {
+        let client = get_group_client(config).await?;
+        client.list_group_issues(group, &params).await?
+    } else {
+        let client = get_client(config, project).await?;
+        client.list_issues(&params).await?
+    };
+    print_page(&result, &headers, print_issues);
+    Ok(())
+}
+
+async fn handle_board(
+    config: &mut Config,
+    project: Option<&str>,
+    group: Option<&str>,
+    group_by: &str,
+    params: IssueListParams,
+) -> Result<()> {
+    check_choice("group_by", group_by, &["label", "assignee", "milestone"])?;
+    check_choice("state", &params.state, &["opened", "closed", "all"])?;
+    let result = if let Some(group) = group {
+        let client = get_group_client(config).await?;
+        client.list_all_group_issues(group, &params).await?
+    } else {
+        let client = get_client(config, project).await?;
+        client.list_all_issues(&params).await?
+    };
+    print_output(&result, |value| print_issue_board(value, group_by));
     Ok(())
 }
 
 async fn handle_show(config: &mut Config, project: Option<&str>, iid: u64) -> Result<()> {
     let client = get_client(config, project).await?;
-    let result = client.get_issue(iid).await?;
-    println!("{}", serde_json::to_string_pretty(&result)?);
+    // Label colors are only for the text view, as in `mr show`
+    let result = if text_output() {
+        client.get_issue_with_label_details(iid).await
+    } else {
+        client.get_issue(iid).await
+    }
+    .map_err(not_found(|| format!("Issue #{} not found", iid)))?;
+    print_output(&result, print_issue_detail);
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_create(
     config: &mut Config,
     project: Option<&str>,
     title: String,
     description: Option<String>,
+    template: Option<String>,
+    edit: bool,
     labels: Option<String>,
     assignee: Option<String>,
+    weight: Option<u64>,
+    due_date: Option<String>,
 ) -> Result<()> {
     let client = get_client(config, project).await?;
+    let description = resolve_description(
+        &client,
+        TemplateKind::Issue,
+        description,
+        template.as_deref(),
+        edit,
+    )
+    .await?;
+    let assignee_id = resolve_optional_user(&client, assignee.as_deref()).await?;
     let result = client
         .create_issue(
             &title,
             description.as_deref(),
             labels.as_deref(),
-            assignee.as_deref(),
+            assignee_id,
+            weight,
+            due_date.as_deref(),
         )
         .await?;
-    let iid = result["iid"].as_u64().unwrap_or(0);
-    let web_url = result["web_url"].as_str().unwrap_or("");
-    println!("Created issue #{}: {}", iid, title);
-    println!("{}", web_url);
+    print_output(&result, |issue| {
+        let iid = issue["iid"].as_u64().unwrap_or(0);
+        let web_url = issue["web_url"].as_str().unwrap_or("");
+        success!("Created issue #{}: {}", iid, title);
+        println!("{}", web_url);
+    });
     Ok(())
 }
+
+async fn handle_update(
+    config: &mut Config,
+    project: Option<&str>,
+    iid: u64,
+    weight: Option<u64>,
+    due_date: Option<String>,
+) -> Result<()> {
+    if weight.is_none() && due_date.is_none() {
+        bail!("Nothing to update; pass --weight or --due-date");
+    }
+    let mut body = serde_json::json!({});
+    if let Some(weight) = weight {
+        body["weight"] = serde_json::json!(weight);
+    }
+    match due_date.as_deref() {
+        // GitLab clears the due date when given an empty string
+        Some("none") => body["due_date"] = serde_json::json!(""),
+        Some(due) => {
+            check_day("--due-date", due)?;
+            body["due_date"] = serde_json::json!(due);
+        }
+        None => {}
+    }
+    let client = get_client(config, project).await?;
+    let result = client
+        .update_issue(iid, &body)
+        .await
+        .map_err(not_found(|| format!("Issue #{} not found", iid)))?;
+    print_output(&result, |issue| {
+        let title = issue["title"].as_str().unwrap_or("");
+        success!("Updated issue #{}: {}", iid, title);
+    });
+    Ok(())
+}
+
+/// `--weight` for `issue list`: a number, or none/any, which the API spells
+/// `None`/`Any`.
+fn weight_filter(value: &str) -> Result<String> {
+    match value.to_lowercase().as_str() {
+        "none" => Ok("None".to_string()),
+        "any" => Ok("Any".to_string()),
+        _ if value.parse::<u64>().is_ok() => Ok(value.to_string()),
+        _ => bail!(
+            "Invalid weight: '{}' (expected a number, none, or any)",
+            value
+        ),
+    }
+}
+
+/// `--due` for `issue list`; `none` maps to the API's `0` (no due date).
+fn due_filter(value: &str) -> Result<String> {
+    check_choice(
+        "due",
+        value,
+        &[
+            "none", "any", "today", "tomorrow", "overdue", "week", "month",
+        ],
+    )?;
+    Ok(if value == "none" { "0" } else { value }.to_string())
+}
+
+async fn handle_link(
+    config: &mut Config,
+    project: Option<&str>,
+    iid: u64,
+    target_iid: u64,
+    link_type: &str,
+    target_project: Option<&str>,
+) -> Result<()> {
+    match link_type {
+        "relates_to" | "blocks" | "is_blocked_by" => {}
+        _ => bail!(
+            "Invalid link type: '{}' (expected: relates_to, blocks, is_blocked_by)",
+            link_type
+        ),
+    }
+    let client = get_client(config, project).await?;
+    let result = client
+        .create_issue_link(iid, target_project, target_iid, link_type)
+        .await?;
+    let target_title = result["target_issue"]["title"].as_str().unwrap_or("");
+    success!(
+        "Linked #{} {} #{}: {}",
+        iid,
+        link_type,
+        target_iid,
+        target_title
+    );
+    Ok(())
+}
+
+async fn handle_links(config: &mut Config, project: Option<&str>, iid: u64) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client.list_issue_links(iid).await?;
+    print_output(&result, print_issue_links);
+    Ok(())
+}
+
+async fn handle_subscribe(
+    config: &mut Config,
+    project: Option<&str>,
+    iid: u64,
+    subscribe: bool,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = if subscribe {
+        client.subscribe_issue(iid).await
+    } else {
+        client.unsubscribe_issue(iid).await
+    };
+    match (changed(result)?, subscribe) {
+        (true, true) => success!("Subscribed to #{}", iid),
+        (true, false) => success!("Unsubscribed from #{}", iid),
+        (false, true) => success!("Already subscribed to #{}", iid),
+        (false, false) => success!("Not subscribed to #{}", iid),
+    }
+    Ok(())
+}
+
+async fn handle_discussions(
+    config: &mut Config,
+    project: Option<&str>,
+    iid: u64,
+    per_page: u32,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let discussions = client.list_issue_discussions(iid, per_page).await?;
+    if let Some(arr) = discussions.as_array() {
+        let threads: Vec<_> = arr.iter().filter(|d| !is_system_only(d)).collect();
+        if threads.is_empty() {
+            println!("No discussion threads on #{}", iid);
+        } else {
+            for d in &threads {
+                print_discussion_thread(d);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_system_only(d: &serde_json::Value) -> bool {
+    d["notes"]
+        .as_array()
+        .map(|n| {
+            n.iter()
+                .all(|note| note["system"].as_bool().unwrap_or(false))
+        })
+        .unwrap_or(true)
+}
@@ -0,0 +1,80 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::api::ApiError;
+use crate::cli::LabelCommands;
+use crate::config::Config;
+use crate::get_client;
+
+/// One entry of a label import file.
+#[derive(Deserialize)]
+struct LabelSpec {
+    name: String,
+    color: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+pub async fn handle(config: &mut Config, command: LabelCommands) -> Result<()> {
+    match command {
+        LabelCommands::Import { file, project } => {
+            handle_import(config, project.as_deref(), &file).await
+        }
+    }
+}
+
+async fn handle_import(config: &mut Config, project: Option<&str>, file: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let labels: Vec<LabelSpec> = serde_json::from_str(&content).with_context(|| {
+        format!(
+            "{} must be a JSON array of {{name, color, description}} objects",
+            file.display()
+        )
+    })?;
+
+    let client = get_client(config, project).await?;
+    let (mut created, mut existing, mut failed) = (0, 0, 0);
+    for label in &labels {
+        match client
+            .create_label(&label.name, &label.color, label.description.as_deref())
+            .await
+        {
+            Ok(_) => {
+                success!("Created {}", label.name);
+                created += 1;
+            }
+            Err(e) if already_exists(&e) => {
+                success!("Skipped {} (already exists)", label.name);
+                existing += 1;
+            }
+            Err(e) => {
+                eprintln!("{}: {}", label.name, e);
+                failed += 1;
+            }
+        }
+    }
+
+    success!(
+        "{} created, {} already existed, {} failed",
+        created,
+        existing,
+        failed
+    );
+    if failed > 0 {
+        bail!("{} of {} labels failed", failed, labels.len());
+    }
+    Ok(())
+}
+
+/// GitLab answers 409 for a duplicate label name, and some versions a 400
+/// whose message says the title is already taken.
+fn already_exists(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ApiError>().is_some_and(|api_err| {
+        api_err.is_conflict()
+            || (api_err.is_bad_request()
+                && (api_err.body.contains("already exists")
+                    || api_err.body.contains("already been taken")))
+    })
+}
@@ -1,8 +1,251 @@
+/// `println!` for the confirmation line of a write command ("Created !12");
+/// silenced by `--quiet`. Data output and errors keep using `println!`/`eprintln!`.
+macro_rules! success {
+    ($($arg:tt)*) => {
+        if !$crate::commands::print::quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub mod alias;
 pub mod branch;
 pub mod ci;
+pub mod commit;
+pub mod doctor;
 pub mod group;
 pub mod issue;
+pub mod label;
 pub mod mr;
 pub mod print;
 pub mod project;
+pub mod runner;
+pub mod template;
+pub mod time;
+pub mod token;
 pub mod webhook;
+pub mod wiki;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::api::{ApiError, Client};
+
+/// Process exit code when the API rejects the token (401).
+pub const EXIT_UNAUTHORIZED: i32 = 3;
+
+/// Process exit code for a lookup that returned 404.
+pub const EXIT_NOT_FOUND: i32 = 4;
+
+/// A requested resource does not exist. `main` exits with `EXIT_NOT_FOUND`
+/// and prints only the message, so scripts can branch on existence.
+#[derive(Debug)]
+pub struct NotFound(pub String);
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// Turn a 404 from the API into `NotFound` with a readable message; other
+/// errors pass through unchanged.
+pub fn not_found(what: impl FnOnce() -> String) -> impl FnOnce(anyhow::Error) -> anyhow::Error {
+    move |err| match err.downcast_ref::<ApiError>() {
+        Some(api_err) if api_err.is_not_found() => NotFound(what()).into(),
+        _ => err,
+    }
+}
+
+/// The `message` of a GitLab error body, flattening `{"field": ["..."]}`
+/// validation errors; the raw body when it isn't JSON.
+pub(crate) fn api_message(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.to_string();
+    };
+    match &value["message"] {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .map(|(field, errors)| match errors {
+                serde_json::Value::Array(list) => {
+                    let list: Vec<_> = list.iter().filter_map(|e| e.as_str()).collect();
+                    format!("{} {}", field, list.join(", "))
+                }
+                other => format!("{} {}", field, other),
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        _ => body.to_string(),
+    }
+}
+
+/// Fail locally on a value the API would reject or silently ignore, listing
+/// what's accepted: `Invalid sort: 'up' (expected: asc, desc)`.
+pub(crate) fn check_choice(what: &str, value: &str, allowed: &[&str]) -> Result<()> {
+    if !allowed.contains(&value) {
+        bail!(
+            "Invalid {}: '{}' (expected: {})",
+            what,
+            value,
+            allowed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Sort directions accepted by list endpoints.
+pub(crate) const SORT_DIRECTIONS: &[&str] = &["asc", "desc"];
+
+/// `--assignee`/`--reviewer`: a username (with or without `@`), an email, or
+/// a numeric user ID.
+pub(crate) async fn resolve_optional_user(
+    client: &Client,
+    user: Option<&str>,
+) -> Result<Option<u64>> {
+    match user {
+        Some(u) => Ok(Some(client.resolve_user_id(u).await?)),
+        None => Ok(None),
+    }
+}
+
+/// Treat a 304 (e.g. subscribing when already subscribed) as success,
+/// returning `false` when the request changed nothing.
+pub fn changed(result: Result<serde_json::Value>) -> Result<bool> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(err) => match err.downcast_ref::<ApiError>() {
+            Some(api_err) if api_err.is_not_modified() => Ok(false),
+            _ => Err(err),
+        },
+    }
+}
+
+/// Open `initial` in the user's editor ($VISUAL, $EDITOR, or vi) and return
+/// the saved text. `extension` sets the temp file suffix for syntax highlighting.
+pub fn edit_text(initial: &str, extension: &str) -> Result<String> {
+    let (temp, mut file) = create_temp_file(extension)?;
+    file.write_all(initial.as_bytes())?;
+    drop(file);
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&temp.0)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'", editor))?;
+    if !status.success() {
+        bail!("Editor exited with {}", status);
+    }
+    let text = std::fs::read_to_string(&temp.0)?;
+    if text.trim().is_empty() {
+        bail!("Aborting: editor returned empty content");
+    }
+    Ok(text)
+}
+
+/// A temp file that is deleted when dropped, on success and error alike.
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Create a new temp file readable only by the user; the text may be the
+/// config file with its tokens. `create_new` refuses an existing path, so a
+/// file or symlink planted at a guessed name is never written through.
+fn create_temp_file(extension: &str) -> Result<(TempFile, std::fs::File)> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    for attempt in 0..16u32 {
+        let path = std::env::temp_dir().join(format!(
+            "gitlab-cli-{}-{:08x}.{}",
+            std::process::id(),
+            nanos.wrapping_add(attempt.wrapping_mul(0x9e37_79b9)),
+            extension
+        ));
+        match options.open(&path) {
+            Ok(file) => return Ok((TempFile(path), file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(
+                    anyhow::Error::from(e).context(format!("Failed to create {}", path.display()))
+                )
+            }
+        }
+    }
+    bail!(
+        "Failed to create a temp file in {}",
+        std::env::temp_dir().display()
+    )
+}
+
+/// Ask before a destructive action. With `expect`, the user must type that
+/// exact string (e.g. the project path); otherwise `y`/`yes` confirms.
+///
+/// Without a terminal there is nobody to ask: typed confirmations fail so a
+/// script must pass `--yes`, and y/N prompts auto-confirm unless
+/// `GITLAB_CLI_REQUIRE_YES` is set.
+pub fn confirm(prompt: &str, expect: Option<&str>) -> Result<bool> {
+    use std::io::{BufRead, IsTerminal};
+    if !std::io::stdin().is_terminal() {
+        if expect.is_none() && std::env::var_os("GITLAB_CLI_REQUIRE_YES").is_none() {
+            return Ok(true);
+        }
+        bail!("{}: no terminal to confirm on; pass --yes", prompt);
+    }
+    match expect {
+        Some(expected) => eprint!("{}? Type {} to confirm: ", prompt, expected),
+        None => eprint!("{}? [y/N] ", prompt),
+    }
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok(match expect {
+        Some(expected) => answer == expected,
+        None => matches!(answer.to_lowercase().as_str(), "y" | "yes"),
+    })
+}
+
+/// Require a plain `YYYY-MM-DD` date, as used by due dates.
+pub(crate) fn check_day(flag: &str, value: &str) -> Result<()> {
+    if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err() {
+        bail!("Invalid {} date: '{}' (expected YYYY-MM-DD)", flag, value);
+    }
+    Ok(())
+}
+
+/// Accept `YYYY-MM-DD` (midnight UTC) or a full ISO 8601 timestamp and return
+/// the ISO 8601 form the API expects.
+pub(crate) fn parse_date(flag: &str, value: &str) -> Result<String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(format!("{}T00:00:00Z", date));
+    }
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(dt) => Ok(dt.to_rfc3339()),
+        Err(_) => bail!(
+            "Invalid {} date: '{}' (expected YYYY-MM-DD or ISO 8601, e.g. 2025-01-31T12:00:00Z)",
+            flag,
+            value
+        ),
+    }
+}
@@ -1,47 +1,319 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 
-use crate::api::Client;
+use crate::api::{ApiError, Client, TimeTarget};
 use crate::cli::MrCommands;
-use crate::commands::print::print_mrs;
-use crate::get_client;
+use crate::commands::print::{
+    color_enabled, print_approval_rules, print_diff_names, print_diff_stat,
+    print_discussion_thread, print_mr_detail, print_mr_versions, print_mrs, print_output,
+    print_page, print_word_diff, text_output,
+};
+use crate::commands::template::{print_templates, resolve_description, TemplateKind};
+use crate::commands::{
+    changed, check_choice, not_found, resolve_optional_user, time, SORT_DIRECTIONS,
+};
 use crate::{api::MrListParams, config::Config};
+use crate::{get_client, get_group_client};
 
 pub async fn handle(config: &mut Config, command: MrCommands) -> Result<()> {
     match command {
-        MrCommands::List { state, author, created_after, created_before, updated_after, order_by, sort, per_page, project } => {
-            handle_list(config, project.as_deref(), MrListParams { per_page, state, author_username: author, created_after, created_before, updated_after, order_by, sort }).await
+        MrCommands::List {
+            state,
+            author,
+            assignee,
+            created_after,
+            created_before,
+            updated_after,
+            target,
+            approved,
+            draft,
+            no_draft,
+            order_by,
+            sort,
+            per_page,
+            group,
+            long,
+            project,
+        } => {
+            let draft = (draft || no_draft).then_some(draft);
+            handle_list(
+                config,
+                project.as_deref(),
+                group.as_deref(),
+                MrListParams {
+                    per_page,
+                    state,
+                    author_username: author,
+                    assignee_username: assignee,
+                    created_after,
+                    created_before,
+                    updated_after,
+                    order_by,
+                    sort,
+                    target_branch: target,
+                    approved,
+                    draft,
+                },
+                long,
+            )
+            .await
         }
         MrCommands::Show { iid, project } => handle_show(config, project.as_deref(), iid).await,
-        MrCommands::Automerge { iid, keep_branch, project } => handle_automerge(config, project.as_deref(), iid, keep_branch).await,
-        MrCommands::Merge { iid, keep_branch, project } => handle_merge(config, project.as_deref(), iid, keep_branch).await,
-        MrCommands::Diff { iid, json, project } => handle_diff(config, project.as_deref(), iid, json).await,
-        MrCommands::Close { iid, project } => handle_close(config, project.as_deref(), iid).await,
-        MrCommands::Comments { iid, per_page, project } => handle_comments(config, project.as_deref(), iid, per_page).await,
-        MrCommands::Comment { iid, message, project } => handle_comment(config, project.as_deref(), iid, message).await,
-        MrCommands::Approve { iid, project } => handle_approve(config, project.as_deref(), iid).await,
-        MrCommands::Discussions { iid, unresolved, per_page, project } => handle_discussions(config, project.as_deref(), iid, unresolved, per_page).await,
-        MrCommands::CommentInline { iid, file, line, old_line, base_sha, head_sha, start_sha, old_file, message, project } => {
-            handle_comment_inline(config, project.as_deref(), iid, file, line, old_line, base_sha, head_sha, start_sha, old_file, message).await
+        MrCommands::Automerge {
+            iid,
+            cancel: true,
+            project,
+            ..
+        } => handle_cancel_automerge(config, project.as_deref(), iid).await,
+        MrCommands::Automerge {
+            iid,
+            keep_branch,
+            project,
+            ..
+        } => handle_automerge(config, project.as_deref(), iid, keep_branch).await,
+        MrCommands::Merge {
+            iids,
+            stdin,
+            train: true,
+            project,
+            ..
+        } => handle_merge_train(config, project.as_deref(), iids, stdin).await,
+        MrCommands::Merge {
+            iids,
+            stdin,
+            keep_branch,
+            project,
+            ..
+        } => handle_merge(config, project.as_deref(), iids, stdin, keep_branch).await,
+        MrCommands::Diff {
+            iid,
+            json,
+            stat,
+            word_diff,
+            name_only,
+            name_status,
+            version,
+            since_version,
+            project,
+            paths,
+        } => {
+            let names = (name_only || name_status).then_some(name_status);
+            handle_diff(
+                config,
+                project.as_deref(),
+                iid,
+                json,
+                stat,
+                word_diff,
+                names,
+                version,
+                since_version,
+                &paths,
+            )
+            .await
         }
-        MrCommands::Reply { iid, discussion, message, project } => handle_reply(config, project.as_deref(), iid, discussion, message).await,
-        MrCommands::Resolve { iid, discussion, unresolve, project } => handle_resolve(config, project.as_deref(), iid, discussion, unresolve).await,
-        MrCommands::Create { title, description, source, target, auto_merge, keep_branch, project } => {
-            handle_create(config, project.as_deref(), title, description, source, target, auto_merge, keep_branch).await
+        MrCommands::Versions { iid, project } => {
+            handle_versions(config, project.as_deref(), iid).await
+        }
+        MrCommands::Close {
+            iids,
+            stdin,
+            project,
+            ..
+        } => handle_close(config, project.as_deref(), iids, stdin).await,
+        MrCommands::Comments {
+            iid,
+            per_page,
+            system,
+            threaded: true,
+            project,
+            ..
+        } => handle_threaded_comments(config, project.as_deref(), iid, per_page, system).await,
+        MrCommands::Comments {
+            iid,
+            per_page,
+            order_by,
+            sort,
+            system,
+            project,
+            ..
+        } => {
+            handle_comments(
+                config,
+                project.as_deref(),
+                iid,
+                per_page,
+                &order_by,
+                &sort,
+                system,
+            )
+            .await
+        }
+        MrCommands::Comment {
+            iid,
+            message,
+            attach,
+            project,
+            ..
+        } => handle_comment(config, project.as_deref(), iid, message, &attach).await,
+        MrCommands::Approve {
+            iids,
+            stdin,
+            sha,
+            project,
+            ..
+        } => handle_approve(config, project.as_deref(), iids, stdin, sha.as_deref()).await,
+        MrCommands::Subscribe { iid, project } => {
+            handle_subscribe(config, project.as_deref(), iid, true).await
+        }
+        MrCommands::Unsubscribe { iid, project } => {
+            handle_subscribe(config, project.as_deref(), iid, false).await
+        }
+        MrCommands::Time { command } => {
+            time::handle(config, TimeTarget::MergeRequest, command).await
+        }
+        MrCommands::ApprovalRules { iid, project } => {
+            handle_approval_rules(config, project.as_deref(), iid).await
+        }
+        MrCommands::Discussions {
+            iid,
+            unresolved,
+            per_page,
+            project,
+        } => handle_discussions(config, project.as_deref(), iid, unresolved, per_page).await,
+        MrCommands::CommentInline {
+            iid,
+            file,
+            line,
+            old_line,
+            base_sha,
+            head_sha,
+            start_sha,
+            old_file,
+            message,
+            project,
+        } => {
+            handle_comment_inline(
+                config,
+                project.as_deref(),
+                iid,
+                file,
+                line,
+                old_line,
+                base_sha,
+                head_sha,
+                start_sha,
+                old_file,
+                message,
+            )
+            .await
+        }
+        MrCommands::Reply {
+            iid,
+            discussion,
+            message,
+            project,
+        } => handle_reply(config, project.as_deref(), iid, discussion, message).await,
+        MrCommands::Resolve {
+            iid,
+            discussion,
+            unresolve,
+            project,
+        } => handle_resolve(config, project.as_deref(), iid, discussion, unresolve).await,
+        MrCommands::ResolveAll {
+            iid,
+            unresolve_all,
+            per_page,
+            project,
+        } => handle_resolve_all(config, project.as_deref(), iid, !unresolve_all, per_page).await,
+        MrCommands::Create {
+            list_templates: true,
+            project,
+            ..
+        } => {
+            let client = get_client(config, project.as_deref()).await?;
+            print_templates(&client, TemplateKind::MergeRequest).await
+        }
+        MrCommands::Create {
+            title,
+            fill,
+            push,
+            description,
+            template,
+            edit,
+            source,
+            target,
+            assignee,
+            reviewer,
+            auto_merge,
+            keep_branch,
+            project,
+            ..
+        } => {
+            handle_create(
+                config,
+                project.as_deref(),
+                title,
+                fill,
+                push,
+                description,
+                template,
+                edit,
+                source,
+                target,
+                assignee,
+                reviewer,
+                auto_merge,
+                keep_branch,
+            )
+            .await
         }
     }
 }
 
-async fn handle_list(config: &mut Config, project: Option<&str>, params: MrListParams) -> Result<()> {
-    let client = get_client(config, project).await?;
-    let result = client.list_merge_requests(&params).await?;
-    print_mrs(&result);
+async fn handle_list(
+    config: &mut Config,
+    project: Option<&str>,
+    group: Option<&str>,
+    params: MrListParams,
+    long: bool,
+) -> Result<()> {
+    check_choice(
+        "state",
+        &params.state,
+        &["opened", "closed", "merged", "all"],
+    )?;
+    if let Some(order_by) = &params.order_by {
+        check_choice(
+            "order_by",
+            order_by,
+            &["created_at", "updated_at", "merged_at", "title"],
+        )?;
+    }
+    if let Some(sort) = &params.sort {
+        check_choice("sort", sort, SORT_DIRECTIONS)?;
+    }
+    let (result, headers) = if let Some(group) = group {
+        let client = get_group_client(config).await?;
+        client.list_group_merge_requests(group, &params).await?
+    } else {
+        let client = get_client(config, project).await?;
+        client.list_merge_requests(&params).await?
+    };
+    print_page(&result, &headers, |v| print_mrs(v, long));
     Ok(())
 }
 
 async fn handle_show(config: &mut Config, project: Option<&str>, iid: u64) -> Result<()> {
     let client = get_client(config, project).await?;
-    let result = client.get_merge_request(iid).await?;
-    println!("{}", serde_json::to_string_pretty(&result)?);
+    // Label colors are only for the text view; structured output keeps the
+    // plain label names the rest of the API returns
+    let result = if text_output() {
+        client.get_merge_request_with_label_details(iid).await
+    } else {
+        client.get_merge_request(iid).await
+    }
+    .map_err(not_found(|| format!("Merge request !{} not found", iid)))?;
+    print_output(&result, print_mr_detail);
     Ok(())
 }
 
@@ -59,7 +331,7 @@ async fn handle_automerge(
         match client.set_automerge(iid, !keep_branch).await {
             Ok(result) => {
                 let title = result["title"].as_str().unwrap_or("");
-                println!("Auto-merge enabled for !{}: {}", iid, title);
+                success!("Auto-merge enabled for !{}: {}", iid, title);
                 return Ok(());
             }
             Err(e) => {
@@ -85,21 +357,62 @@ async fn handle_automerge(
     Ok(())
 }
 
-async fn handle_merge(
+async fn handle_cancel_automerge(
     config: &mut Config,
     project: Option<&str>,
     iid: u64,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let mr = client.get_merge_request(iid).await?;
+    if !mr["merge_when_pipeline_succeeds"]
+        .as_bool()
+        .unwrap_or(false)
+    {
+        success!("Auto-merge is not enabled for !{}", iid);
+        return Ok(());
+    }
+    client.cancel_automerge(iid).await?;
+    success!("Auto-merge cancelled for !{}", iid);
+    Ok(())
+}
+
+async fn handle_merge(
+    config: &mut Config,
+    project: Option<&str>,
+    iids: Vec<u64>,
+    stdin: bool,
     keep_branch: bool,
 ) -> Result<()> {
+    let iids = collect_iids(iids, stdin)?;
     let client = get_client(config, project).await?;
+    run_bulk(&iids, |iid| merge_mr(&client, iid, keep_branch)).await
+}
+
+async fn merge_mr(client: &Client, iid: u64, keep_branch: bool) -> Result<()> {
     match client.merge_merge_request(iid, !keep_branch).await {
         Ok(result) => {
-            let title = result["title"].as_str().unwrap_or("");
-            println!("Merged !{}: {}", iid, title);
+            print_output(&result, |mr| {
+                let title = mr["title"].as_str().unwrap_or("");
+                success!("Merged !{}: {}", iid, title);
+            });
             Ok(())
         }
         Err(e) => {
             let err_str = e.to_string();
+            let refused = e
+                .downcast_ref::<ApiError>()
+                .is_some_and(|api_err| matches!(api_err.status.as_u16(), 405 | 406 | 422));
+            if refused
+                && (err_str.to_lowercase().contains("merge train")
+                    || uses_merge_trains(client).await)
+            {
+                bail!(
+                    "Cannot merge !{}: the project merges through a merge train; \
+                     use `gitlab mr merge {} --train`",
+                    iid,
+                    iid
+                );
+            }
             if err_str.contains("405") {
                 bail!(
                     "Cannot merge !{}: MR is not in a mergeable state \
@@ -115,24 +428,177 @@ async fn handle_merge(
     }
 }
 
+/// Whether the project has merge trains enabled, so a rejected merge can
+/// point at `--train`. Lookup failures count as no.
+async fn uses_merge_trains(client: &Client) -> bool {
+    client
+        .get_project()
+        .await
+        .map(|project| project["merge_trains_enabled"].as_bool().unwrap_or(false))
+        .unwrap_or(false)
+}
+
+async fn handle_merge_train(
+    config: &mut Config,
+    project: Option<&str>,
+    iids: Vec<u64>,
+    stdin: bool,
+) -> Result<()> {
+    let iids = collect_iids(iids, stdin)?;
+    let client = get_client(config, project).await?;
+    run_bulk(&iids, |iid| add_to_train(&client, iid)).await
+}
+
+async fn add_to_train(client: &Client, iid: u64) -> Result<()> {
+    let cars = client.add_to_merge_train(iid).await?;
+    let target = match cars[0]["target_branch"].as_str() {
+        Some(branch) => branch.to_string(),
+        None => client.get_merge_request(iid).await?["target_branch"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    };
+    let train = client.list_merge_train(&target).await?;
+    let cars = train.as_array().map_or(&[][..], Vec::as_slice);
+    let position = cars
+        .iter()
+        .position(|car| car["merge_request"]["iid"].as_u64() == Some(iid))
+        .map(|index| index + 1);
+    let result = serde_json::json!({
+        "iid": iid,
+        "target_branch": target,
+        "position": position,
+        "train_length": cars.len(),
+    });
+    print_output(&result, |_| match position {
+        Some(position) => success!(
+            "Added !{} to the {} merge train at position {} of {}",
+            iid,
+            target,
+            position,
+            cars.len()
+        ),
+        None => success!("Added !{} to the {} merge train", iid, target),
+    });
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_diff(
     config: &mut Config,
     project: Option<&str>,
     iid: u64,
     json: bool,
+    stat: bool,
+    word_diff: bool,
+    names: Option<bool>,
+    version: Option<u64>,
+    since_version: Option<u64>,
+    paths: &[String],
 ) -> Result<()> {
     let client = get_client(config, project).await?;
-    let result = client.get_merge_request_changes(iid).await?;
+    let mut result = match (since_version, version) {
+        (Some(since), to) => diff_between_versions(&client, iid, since, to).await?,
+        (None, Some(version_id)) => {
+            let mut version =
+                client
+                    .get_mr_version_diff(iid, version_id)
+                    .await
+                    .map_err(not_found(|| {
+                        format!("Version {} of !{} not found", version_id, iid)
+                    }))?;
+            // Versions call the change list `diffs`; the printers expect `changes`
+            version["changes"] = version["diffs"].take();
+            version
+        }
+        (None, None) => client.get_merge_request_changes(iid).await?,
+    };
+    filter_changes_by_path(&mut result, paths);
 
     if json {
         println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if stat {
+        print_diff_stat(&result["changes"]);
+    } else if let Some(with_status) = names {
+        print_diff_names(&result["changes"], with_status);
     } else {
-        print_diff_changes(&result);
+        print_diff_changes(&result, word_diff);
     }
     Ok(())
 }
 
-fn print_diff_changes(result: &serde_json::Value) {
+/// What changed between two pushes: the head commit of version `since`
+/// compared with the head of version `to` (the latest if omitted).
+async fn diff_between_versions(
+    client: &Client,
+    iid: u64,
+    since: u64,
+    to: Option<u64>,
+) -> Result<serde_json::Value> {
+    let versions = client
+        .list_mr_versions(iid)
+        .await
+        .map_err(not_found(|| format!("Merge request !{} not found", iid)))?;
+    let versions = versions.as_array().map(Vec::as_slice).unwrap_or_default();
+    let head = |id: Option<u64>| -> Result<String> {
+        let version = match id {
+            Some(id) => versions.iter().find(|v| v["id"].as_u64() == Some(id)),
+            // Versions are listed newest first
+            None => versions.first(),
+        };
+        version
+            .and_then(|v| v["head_commit_sha"].as_str())
+            .map(String::from)
+            .ok_or_else(|| match id {
+                Some(id) => anyhow!("Version {} of !{} not found", id, iid),
+                None => anyhow!("!{} has no diff versions", iid),
+            })
+    };
+    let (from, to) = (head(Some(since))?, head(to)?);
+    let mut result = client.compare_refs(&from, &to).await?;
+    // Compare calls the change list `diffs`; the printers expect `changes`
+    result["changes"] = result["diffs"].take();
+    Ok(result)
+}
+
+async fn handle_versions(config: &mut Config, project: Option<&str>, iid: u64) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client
+        .list_mr_versions(iid)
+        .await
+        .map_err(not_found(|| format!("Merge request !{} not found", iid)))?;
+    print_output(&result, print_mr_versions);
+    Ok(())
+}
+
+/// Keep only changes whose old or new path is one of `paths` or lies under it.
+fn filter_changes_by_path(result: &mut serde_json::Value, paths: &[String]) {
+    if paths.is_empty() {
+        return;
+    }
+    if let Some(changes) = result["changes"].as_array_mut() {
+        changes.retain(|change| {
+            ["new_path", "old_path"].iter().any(|key| {
+                let path = change[*key].as_str().unwrap_or("");
+                paths.iter().any(|p| path_is_under(path, p))
+            })
+        });
+    }
+}
+
+/// Whether `path` is `dir` or inside it, matching whole components so that
+/// `src` does not match `src2/main.rs`.
+fn path_is_under(path: &str, dir: &str) -> bool {
+    let dir = dir.trim_end_matches('/');
+    if dir.is_empty() {
+        return true;
+    }
+    path.strip_prefix(dir)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+fn print_diff_changes(result: &serde_json::Value, word_diff: bool) {
+    let color = word_diff && color_enabled();
     if let Some(changes) = result["changes"].as_array() {
         for change in changes {
             let old_path = change["old_path"].as_str().unwrap_or("");
@@ -141,18 +607,34 @@ fn print_diff_changes(result: &serde_json::Value) {
 
             println!("--- a/{}", old_path);
             println!("+++ b/{}", new_path);
-            print!("{}", diff);
+            if word_diff {
+                print_word_diff(diff, color);
+            } else {
+                print!("{}", diff);
+            }
         }
     }
 }
 
-async fn handle_close(config: &mut Config, project: Option<&str>, iid: u64) -> Result<()> {
+async fn handle_close(
+    config: &mut Config,
+    project: Option<&str>,
+    iids: Vec<u64>,
+    stdin: bool,
+) -> Result<()> {
+    let iids = collect_iids(iids, stdin)?;
     let client = get_client(config, project).await?;
+    run_bulk(&iids, |iid| close_mr(&client, iid)).await
+}
+
+async fn close_mr(client: &Client, iid: u64) -> Result<()> {
     let result = client
         .update_merge_request(iid, &serde_json::json!({"state_event": "close"}))
         .await?;
-    let title = result["title"].as_str().unwrap_or("");
-    println!("Closed !{}: {}", iid, title);
+    print_output(&result, |mr| {
+        let title = mr["title"].as_str().unwrap_or("");
+        success!("Closed !{}: {}", iid, title);
+    });
     Ok(())
 }
 
@@ -161,32 +643,81 @@ async fn handle_comments(
     project: Option<&str>,
     iid: u64,
     per_page: u32,
+    order_by: &str,
+    sort: &str,
+    system: bool,
 ) -> Result<()> {
+    check_choice("order_by", order_by, &["created_at", "updated_at"])?;
+    check_choice("sort", sort, SORT_DIRECTIONS)?;
     let client = get_client(config, project).await?;
-    let notes = client.list_mr_notes(iid, per_page).await?;
+    let notes = client.list_mr_notes(iid, per_page, order_by, sort).await?;
     if let Some(arr) = notes.as_array() {
         if arr.is_empty() {
             println!("No comments on !{}", iid);
         } else {
             for note in arr {
-                print_mr_note(note);
+                print_mr_note(note, system);
             }
         }
     }
     Ok(())
 }
 
-fn print_mr_note(note: &serde_json::Value) {
+/// Every discussion on the MR in thread order; `per_page` is the page size.
+/// Threads made up only of system notes are dropped unless `show_system`.
+async fn handle_threaded_comments(
+    config: &mut Config,
+    project: Option<&str>,
+    iid: u64,
+    per_page: u32,
+    show_system: bool,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let discussions = client.list_mr_discussions(iid, per_page).await?;
+    if let Some(arr) = discussions.as_array() {
+        let threads: Vec<_> = arr
+            .iter()
+            .filter(|d| {
+                show_system
+                    || d["notes"].as_array().is_some_and(|notes| {
+                        notes
+                            .iter()
+                            .any(|n| !n["system"].as_bool().unwrap_or(false))
+                    })
+            })
+            .collect();
+        if threads.is_empty() {
+            println!("No comments on !{}", iid);
+        } else {
+            for d in threads {
+                print_discussion_thread(d);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print one note; system notes are skipped unless `show_system` is set, in
+/// which case they are tagged `[system]` and dimmed on a terminal.
+fn print_mr_note(note: &serde_json::Value, show_system: bool) {
     let system = note["system"].as_bool().unwrap_or(false);
-    if system {
+    if system && !show_system {
         return;
     }
     let id = note["id"].as_u64().unwrap_or(0);
     let author = note["author"]["username"].as_str().unwrap_or("?");
     let created = note["created_at"].as_str().unwrap_or("?");
     let body = note["body"].as_str().unwrap_or("");
-    println!("--- #{} by @{} ({})", id, author, created);
-    println!("{}", body);
+    if !system {
+        println!("--- #{} by @{} ({})", id, author, created);
+        println!("{}", body);
+    } else if color_enabled() {
+        println!("\x1b[2m--- #{} [system] by @{} ({})", id, author, created);
+        println!("{}\x1b[0m", body);
+    } else {
+        println!("--- #{} [system] by @{} ({})", id, author, created);
+        println!("{}", body);
+    }
     println!();
 }
 
@@ -195,22 +726,158 @@ async fn handle_comment(
     project: Option<&str>,
     iid: u64,
     message: Option<String>,
+    attach: &[std::path::PathBuf],
 ) -> Result<()> {
     let client = get_client(config, project).await?;
-    let body = read_message(message)?;
-    if body.trim().is_empty() {
+    let mut body = read_message(message)?;
+    if body.trim().is_empty() && attach.is_empty() {
         bail!("Comment body is empty");
     }
+    for path in attach {
+        let upload = client.upload_file(path).await?;
+        let markdown = upload["markdown"]
+            .as_str()
+            .with_context(|| format!("Upload of {} returned no markdown link", path.display()))?;
+        if !body.trim().is_empty() {
+            body = format!("{}\n\n", body.trim_end());
+        }
+        body.push_str(markdown);
+    }
     let result = client.create_mr_note(iid, &body).await?;
-    let note_id = result["id"].as_u64().unwrap_or(0);
-    println!("Comment #{} added to !{}", note_id, iid);
+    print_output(&result, |note| {
+        let note_id = note["id"].as_u64().unwrap_or(0);
+        success!("Comment #{} added to !{}", note_id, iid);
+    });
     Ok(())
 }
 
-async fn handle_approve(config: &mut Config, project: Option<&str>, iid: u64) -> Result<()> {
+async fn handle_approve(
+    config: &mut Config,
+    project: Option<&str>,
+    iids: Vec<u64>,
+    stdin: bool,
+    sha: Option<&str>,
+) -> Result<()> {
+    let iids = collect_iids(iids, stdin)?;
+    if sha.is_some() && iids.len() > 1 {
+        bail!("--sha can only be used when approving a single merge request");
+    }
     let client = get_client(config, project).await?;
-    client.approve_merge_request(iid).await?;
-    println!("Approved !{}", iid);
+    run_bulk(&iids, |iid| approve_mr(&client, iid, sha)).await
+}
+
+/// Approve `iid` pinned to a head SHA so a push made after review is not
+/// approved implicitly.
+async fn approve_mr(client: &Client, iid: u64, sha: Option<&str>) -> Result<()> {
+    let sha = match sha {
+        Some(sha) => sha.to_string(),
+        None => {
+            let mr = client.get_merge_request(iid).await?;
+            mr["sha"]
+                .as_str()
+                .with_context(|| format!("!{} has no head commit", iid))?
+                .to_string()
+        }
+    };
+    let result = client
+        .approve_merge_request(iid, &sha)
+        .await
+        .map_err(|err| match err.downcast_ref::<ApiError>() {
+            Some(api_err) if api_err.is_conflict() => anyhow!(
+                "!{} was not approved: its head is no longer {}; review the new changes and approve again",
+                iid,
+                short_sha(&sha)
+            ),
+            _ => err,
+        })?;
+    print_output(&result, |_| {
+        success!("Approved !{} at {}", iid, short_sha(&sha))
+    });
+    Ok(())
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..8).unwrap_or(sha)
+}
+
+/// Combine positional IIDs with newline-separated IIDs read from stdin.
+fn collect_iids(mut iids: Vec<u64>, stdin: bool) -> Result<Vec<u64>> {
+    if stdin {
+        let input = read_message(None)?;
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let iid = line
+                .trim_start_matches('!')
+                .parse()
+                .with_context(|| format!("Invalid MR IID: {}", line))?;
+            iids.push(iid);
+        }
+    }
+    if iids.is_empty() {
+        bail!("No merge request IIDs given");
+    }
+    Ok(iids)
+}
+
+/// Run an operation for each IID, reporting failures without stopping.
+/// A single IID keeps the plain error behaviour.
+async fn run_bulk<F, Fut>(iids: &[u64], op: F) -> Result<()>
+where
+    F: Fn(u64) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    if let [iid] = iids {
+        return op(*iid).await;
+    }
+    let mut failed = 0;
+    for &iid in iids {
+        if let Err(e) = op(iid).await {
+            eprintln!("!{}: {}", iid, e);
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        bail!("{} of {} merge requests failed", failed, iids.len());
+    }
+    Ok(())
+}
+
+async fn handle_subscribe(
+    config: &mut Config,
+    project: Option<&str>,
+    iid: u64,
+    subscribe: bool,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = if subscribe {
+        client.subscribe_merge_request(iid).await
+    } else {
+        client.unsubscribe_merge_request(iid).await
+    };
+    match (changed(result)?, subscribe) {
+        (true, true) => success!("Subscribed to !{}", iid),
+        (true, false) => success!("Unsubscribed from !{}", iid),
+        (false, true) => success!("Already subscribed to !{}", iid),
+        (false, false) => success!("Not subscribed to !{}", iid),
+    }
+    Ok(())
+}
+
+async fn handle_approval_rules(config: &mut Config, project: Option<&str>, iid: u64) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = match client.list_mr_approval_rules(iid).await {
+        Ok(result) => result,
+        Err(e) => {
+            match e.downcast_ref::<ApiError>() {
+                // Approval rules are a paid feature; Free instances answer 403/404
+                Some(api_err) if api_err.is_not_found() || api_err.is_forbidden() => {
+                    println!("Approval rules are not available for this project (GitLab Premium feature)");
+                    return Ok(());
+                }
+                _ => return Err(e),
+            }
+        }
+    };
+    print_output(&result, print_approval_rules);
     Ok(())
 }
 
@@ -265,42 +932,6 @@ fn is_visible_thread(d: &serde_json::Value, unresolved: bool) -> bool {
     }
 }
 
-fn print_discussion_thread(d: &serde_json::Value) {
-    let disc_id = d["id"].as_str().unwrap_or("?");
-    let notes = d["notes"].as_array();
-    let first = notes.and_then(|n| n.first());
-
-    let position = first.and_then(|n| n["position"].as_object());
-    if let Some(pos) = position {
-        let path = pos
-            .get("new_path")
-            .or(pos.get("old_path"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("?");
-        let line = pos
-            .get("new_line")
-            .or(pos.get("old_line"))
-            .and_then(|v| v.as_u64())
-            .map(|l| l.to_string())
-            .unwrap_or_default();
-        println!("--- {} ({}:{})", disc_id, path, line);
-    } else {
-        println!("--- {}", disc_id);
-    }
-
-    let resolved = first.and_then(|n| n["resolved"].as_bool()).unwrap_or(false);
-    println!("  resolved: {}", resolved);
-
-    if let Some(notes_arr) = notes {
-        for note in notes_arr {
-            let author = note["author"]["username"].as_str().unwrap_or("?");
-            let body = note["body"].as_str().unwrap_or("");
-            println!("  @{}: {}", author, body);
-        }
-    }
-    println!();
-}
-
 #[allow(clippy::too_many_arguments)]
 async fn handle_comment_inline(
     config: &mut Config,
@@ -323,12 +954,23 @@ async fn handle_comment_inline(
     if line.is_none() && old_line.is_none() {
         bail!("Either --line or --old-line must be specified");
     }
-    let position = build_inline_position(&file, old_file.as_deref(), line, old_line, &base_sha, &head_sha, &start_sha);
+    let position = build_inline_position(
+        &file,
+        old_file.as_deref(),
+        line,
+        old_line,
+        &base_sha,
+        &head_sha,
+        &start_sha,
+    );
     let result = client.create_mr_discussion(iid, &body, &position).await?;
     let disc_id = result["id"].as_str().unwrap_or("?");
-    println!(
+    success!(
         "Inline comment added to !{} at {}:{} (discussion {})",
-        iid, file, line.or(old_line).unwrap_or(0), disc_id
+        iid,
+        file,
+        line.or(old_line).unwrap_or(0),
+        disc_id
     );
     Ok(())
 }
@@ -374,9 +1016,11 @@ async fn handle_reply(
     }
     let result = client.reply_to_discussion(iid, &discussion, &body).await?;
     let note_id = result["id"].as_u64().unwrap_or(0);
-    println!(
+    success!(
         "Reply #{} added to discussion {} on !{}",
-        note_id, discussion, iid
+        note_id,
+        discussion,
+        iid
     );
     Ok(())
 }
@@ -394,33 +1038,131 @@ async fn handle_resolve(
         .resolve_discussion(iid, &discussion, resolved)
         .await?;
     let action = if resolved { "Resolved" } else { "Unresolved" };
-    println!("{} discussion {} on !{}", action, discussion, iid);
+    success!("{} discussion {} on !{}", action, discussion, iid);
+    Ok(())
+}
+
+async fn handle_resolve_all(
+    config: &mut Config,
+    project: Option<&str>,
+    iid: u64,
+    resolved: bool,
+    per_page: u32,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let discussions = client.list_mr_discussions(iid, per_page).await?;
+    let threads: Vec<&serde_json::Value> = discussions
+        .as_array()
+        .map(|arr| arr.iter().filter(|d| is_resolvable_thread(d)).collect())
+        .unwrap_or_default();
+
+    let (pending, skipped): (Vec<_>, Vec<_>) = threads
+        .into_iter()
+        .partition(|d| is_visible_thread(d, true) == resolved);
+
+    let mut failed = 0;
+    for d in &pending {
+        let disc_id = d["id"].as_str().unwrap_or("?");
+        if let Err(e) = client.resolve_discussion(iid, disc_id, resolved).await {
+            eprintln!("{}: {}", disc_id, e);
+            failed += 1;
+        }
+    }
+
+    let action = if resolved { "Resolved" } else { "Unresolved" };
+    let state = if resolved { "resolved" } else { "unresolved" };
+    success!(
+        "{} {} thread(s) on !{} ({} already {})",
+        action,
+        pending.len() - failed,
+        iid,
+        skipped.len(),
+        state
+    );
+    if failed > 0 {
+        bail!("{} of {} threads failed", failed, pending.len());
+    }
     Ok(())
 }
 
+fn is_resolvable_thread(d: &serde_json::Value) -> bool {
+    is_visible_thread(d, false)
+        && d["notes"]
+            .as_array()
+            .map(|n| {
+                n.iter()
+                    .any(|note| note["resolvable"].as_bool().unwrap_or(false))
+            })
+            .unwrap_or(false)
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn handle_create(
     config: &mut Config,
     project: Option<&str>,
-    title: String,
+    title: Option<String>,
+    fill: bool,
+    push: bool,
     description: Option<String>,
+    template: Option<String>,
+    edit: bool,
     source: Option<String>,
     target: Option<String>,
+    assignee: Option<String>,
+    reviewer: Option<String>,
     auto_merge: bool,
     keep_branch: bool,
 ) -> Result<()> {
     let source_branch = resolve_source_branch(source)?;
     let client = get_client(config, project).await?;
     let target_branch = resolve_target_branch(&client, target).await?;
+    let (title, description) = if fill {
+        let (fill_title, fill_description) = fill_from_commits(&target_branch, &source_branch)?;
+        (
+            title.unwrap_or(fill_title),
+            description.or(fill_description),
+        )
+    } else {
+        (title.context("--title is required")?, description)
+    };
+    if push {
+        push_branch(&source_branch)?;
+    }
+    let assignee_id = resolve_optional_user(&client, assignee.as_deref()).await?;
+    let reviewer_id = resolve_optional_user(&client, reviewer.as_deref()).await?;
+    let description = resolve_description(
+        &client,
+        TemplateKind::MergeRequest,
+        description,
+        template.as_deref(),
+        edit,
+    )
+    .await?;
 
     let result = client
-        .create_merge_request(&title, &source_branch, &target_branch, description.as_deref())
-        .await?;
+        .create_merge_request(
+            &title,
+            &source_branch,
+            &target_branch,
+            description.as_deref(),
+            assignee_id,
+            reviewer_id,
+        )
+        .await
+        .map_err(|err| match err.downcast_ref::<ApiError>() {
+            Some(api_err) if !push && is_missing_source_branch(api_err) => anyhow!(
+                "Source branch {} does not exist on GitLab; push it first or rerun with --push",
+                source_branch
+            ),
+            _ => err,
+        })?;
 
     let iid = result["iid"].as_u64().unwrap_or(0);
-    let web_url = result["web_url"].as_str().unwrap_or("");
-    println!("Created !{}: {}", iid, title);
-    println!("{}", web_url);
+    print_output(&result, |mr| {
+        let web_url = mr["web_url"].as_str().unwrap_or("");
+        success!("Created !{}: {}", iid, title);
+        println!("{}", web_url);
+    });
 
     if auto_merge {
         enable_automerge_after_create(&client, iid, keep_branch).await;
@@ -442,6 +1184,80 @@ fn resolve_source_branch(source: Option<String>) -> Result<String> {
     Ok(String::from_utf8(output.stdout)?.trim().to_string())
 }
 
+fn push_branch(branch: &str) -> Result<()> {
+    let status = std::process::Command::new("git")
+        .args(["push", "-u", "origin", branch])
+        .status()
+        .context("Failed to run git push")?;
+    if !status.success() {
+        bail!("git push -u origin {} failed", branch);
+    }
+    Ok(())
+}
+
+/// GitLab rejects an MR whose source branch is not on the server with a
+/// 400/404 naming the source branch.
+fn is_missing_source_branch(err: &ApiError) -> bool {
+    let body = err.body.to_lowercase();
+    (err.is_bad_request() || err.is_not_found())
+        && body.contains("source branch")
+        && (body.contains("does not exist") || body.contains("doesn't exist"))
+}
+
+/// Title from the newest commit subject on `source` that is not on `target`,
+/// description from the bodies of those commits, oldest first. Compares
+/// against `origin/<target>` when that ref exists locally.
+fn fill_from_commits(target: &str, source: &str) -> Result<(String, Option<String>)> {
+    let remote_target = format!("origin/{}", target);
+    let has_remote = std::process::Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", &remote_target])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    let base = if has_remote {
+        remote_target.as_str()
+    } else {
+        target
+    };
+
+    let output = std::process::Command::new("git")
+        .args([
+            "log",
+            "--format=%s%x1f%b%x1e",
+            &format!("{}..{}", base, source),
+        ])
+        .output()
+        .context("Failed to run git log")?;
+    if !output.status.success() {
+        bail!(
+            "git log {}..{} failed: {}",
+            base,
+            source,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let log = String::from_utf8(output.stdout)?;
+    let commits: Vec<(&str, &str)> = log
+        .split('\x1e')
+        .filter_map(|entry| entry.trim_start_matches('\n').split_once('\x1f'))
+        .collect();
+    let Some((title, _)) = commits.first() else {
+        bail!(
+            "No commits on {} that are not on {}; nothing to fill from",
+            source,
+            base
+        );
+    };
+
+    let bodies: Vec<&str> = commits
+        .iter()
+        .rev()
+        .map(|(_, body)| body.trim())
+        .filter(|body| !body.is_empty())
+        .collect();
+    let description = (!bodies.is_empty()).then(|| bodies.join("\n\n"));
+    Ok((title.trim().to_string(), description))
+}
+
 async fn resolve_target_branch(client: &Client, target: Option<String>) -> Result<String> {
     if let Some(t) = target {
         return Ok(t);
@@ -456,7 +1272,9 @@ async fn resolve_target_branch(client: &Client, target: Option<String>) -> Resul
 async fn enable_automerge_after_create(client: &Client, iid: u64, keep_branch: bool) {
     tokio::time::sleep(std::time::Duration::from_secs(2)).await;
     match client.set_automerge(iid, !keep_branch).await {
-        Ok(_) => println!("Auto-merge enabled"),
+        // Keep stdout to the created MR under --output json/yaml/template
+        Ok(_) if !text_output() => eprintln!("Auto-merge enabled"),
+        Ok(_) => success!("Auto-merge enabled"),
         Err(e) => {
             eprintln!("Warning: Could not enable auto-merge: {}", e);
             eprintln!(
@@ -478,3 +1296,19 @@ fn read_message(message: Option<String>) -> Result<String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_is_under_matches_whole_components() {
+        assert!(path_is_under("src/main.rs", "src"));
+        assert!(path_is_under("src/main.rs", "src/"));
+        assert!(path_is_under("src/main.rs", "src/main.rs"));
+        assert!(path_is_under("src/api/mod.rs", "src/api//"));
+        assert!(!path_is_under("src2/main.rs", "src"));
+        assert!(!path_is_under("src/main.rs.orig", "src/main.rs"));
+        assert!(!path_is_under("lib/src/main.rs", "src"));
+    }
+}
@@ -1,40 +1,754 @@
 use serde_json::Value;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
-pub fn print_mrs(value: &Value) {
+use crate::api::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// Text, but table columns are never truncated to fit the terminal
+    Wide,
+    Json,
+    Yaml,
+    /// One line per item from a `template:{field} {nested.field}` format string
+    Template(Vec<TemplatePart>),
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "wide" => Ok(Self::Wide),
+            "json" => Ok(Self::Json),
+            "yaml" => Ok(Self::Yaml),
+            _ => match s.strip_prefix("template:") {
+                Some(template) => parse_template(template).map(Self::Template),
+                None => Err(format!(
+                    "invalid output format '{}' (expected: text, wide, json, yaml, template:<format>)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePart {
+    Literal(String),
+    Field(String),
+}
+
+/// Split a format string into literals and `{dotted.path}` placeholders.
+/// `{{` and `}}` produce literal braces.
+fn parse_template(template: &str) -> Result<Vec<TemplatePart>, String> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => {
+                            return Err(format!("unclosed '{{' in template '{}'", template))
+                        }
+                        Some(c) => field.push(c),
+                    }
+                }
+                if !literal.is_empty() {
+                    parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(TemplatePart::Field(field.trim().to_string()));
+            }
+            '}' => {
+                return Err(format!(
+                    "unmatched '}}' in template '{}' (use '}}}}')",
+                    template
+                ))
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    Ok(parts)
+}
+
+/// Look up a dotted path like `author.username` or `labels.0`.
+fn value_at_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(value, |current, key| match current {
+            Value::Object(map) => map.get(key),
+            Value::Array(arr) => key.parse::<usize>().ok().and_then(|i| arr.get(i)),
+            _ => None,
+        })
+}
+
+fn template_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(arr) => arr.iter().map(template_value).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+fn render_template(parts: &[TemplatePart], item: &Value) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            TemplatePart::Literal(s) => s.clone(),
+            TemplatePart::Field(path) => value_at_path(item, path)
+                .map(template_value)
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn print_template(parts: &[TemplatePart], value: &Value) {
+    match value.as_array() {
+        Some(items) => {
+            for item in items {
+                println!("{}", render_template(parts, item));
+            }
+        }
+        None => println!("{}", render_template(parts, value)),
+    }
+}
+
+#[derive(Default)]
+struct OutputOptions {
+    format: OutputFormat,
+    with_meta: bool,
+    quiet: bool,
+}
+
+static OUTPUT: OnceLock<OutputOptions> = OnceLock::new();
+
+/// Set the output options for this run. Called once from `main`.
+pub fn init_output(format: OutputFormat, with_meta: bool, quiet: bool) {
+    let _ = OUTPUT.set(OutputOptions {
+        format,
+        with_meta,
+        quiet,
+    });
+}
+
+fn output() -> &'static OutputOptions {
+    OUTPUT.get_or_init(OutputOptions::default)
+}
+
+/// Whether `--quiet` was given; see the `success!` macro.
+pub fn quiet() -> bool {
+    output().quiet
+}
+
+/// Whether `--with-meta` was given; see `print_page`.
+pub fn with_meta() -> bool {
+    output().with_meta
+}
+
+/// Whether the selected format is human-readable text (`text` or `wide`).
+pub fn text_output() -> bool {
+    matches!(output().format, OutputFormat::Text | OutputFormat::Wide)
+}
+
+/// Print a value in the selected output format, using `text` for human output.
+pub fn print_output(value: &Value, text: impl Fn(&Value)) {
+    match &output().format {
+        OutputFormat::Json => print_json(value),
+        OutputFormat::Yaml => print_yaml(value),
+        OutputFormat::Text | OutputFormat::Wide => text(value),
+        OutputFormat::Template(parts) => print_template(parts, value),
+    }
+}
+
+/// Like `print_output` for one page of a list. With `--with-meta`, JSON and
+/// YAML output is wrapped with the pagination headers.
+pub fn print_page(items: &Value, headers: &HeaderMap, text: impl Fn(&Value)) {
+    match output().format {
+        OutputFormat::Json if output().with_meta => {
+            print_json(&with_pagination_meta(items, headers))
+        }
+        OutputFormat::Yaml if output().with_meta => {
+            print_yaml(&with_pagination_meta(items, headers))
+        }
+        _ => print_output(items, text),
+    }
+}
+
+fn with_pagination_meta(items: &Value, headers: &HeaderMap) -> Value {
+    let header_u64 = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    serde_json::json!({
+        "items": items,
+        "total": header_u64("x-total"),
+        "next_page": header_u64("x-next-page"),
+    })
+}
+
+fn print_json(value: &Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    );
+}
+
+fn print_yaml(value: &Value) {
+    match serde_yaml::to_string(value) {
+        Ok(yaml) => print!("{}", yaml),
+        Err(_) => print_json(value),
+    }
+}
+
+/// Columns are never shrunk below this many characters.
+const MIN_COLUMN_WIDTH: usize = 8;
+
+fn terminal_width() -> Option<usize> {
+    if !crate::pager::stdout_is_terminal() {
+        return None;
+    }
+    // Under the pager stdout is a pipe, so fall back to stderr's terminal
+    terminal_size::terminal_size()
+        .or_else(|| terminal_size::terminal_size_of(std::io::stderr()))
+        .map(|(terminal_size::Width(w), _)| w as usize)
+}
+
+/// Print rows as left-aligned columns sized to their widest value, with an
+/// optional header row underlined by dashes. When the table is wider than
+/// the terminal, the widest columns are truncated with `…` (not with
+/// `--output wide` or when output is not a terminal).
+pub fn print_table(header: Option<&[&str]>, rows: &[Vec<String>]) {
+    let header: Option<Vec<String>> = header.map(|h| h.iter().map(|c| c.to_string()).collect());
+    let all_rows = || header.iter().chain(rows.iter());
+    let columns = all_rows().map(|r| r.len()).max().unwrap_or(0);
+    if columns == 0 {
+        return;
+    }
+    let mut widths = vec![0; columns];
+    for row in all_rows() {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let limit = match output().format {
+        OutputFormat::Wide => None,
+        _ => terminal_width(),
+    };
+    if let Some(limit) = limit {
+        let total = |widths: &[usize]| widths.iter().sum::<usize>() + columns - 1;
+        while total(&widths) > limit {
+            let (widest, &width) = widths.iter().enumerate().max_by_key(|&(_, w)| *w).unwrap();
+            if width <= MIN_COLUMN_WIDTH {
+                break;
+            }
+            widths[widest] -= 1;
+        }
+    }
+
+    let render = |row: &Vec<String>| {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let cell = truncate_cell(cell, widths[i]);
+                if i + 1 == row.len() {
+                    cell
+                } else {
+                    format!("{:<width$}", cell, width = widths[i])
+                }
+            })
+            .collect();
+        cells.join(" ").trim_end().to_string()
+    };
+
+    if let Some(header) = &header {
+        println!("{}", render(header));
+        println!("{}", "-".repeat(widths.iter().sum::<usize>() + columns - 1));
+    }
+    for row in rows {
+        println!("{}", render(row));
+    }
+}
+
+fn truncate_cell(cell: &str, width: usize) -> String {
+    if cell.chars().count() <= width {
+        return cell.to_string();
+    }
+    let mut truncated: String = cell.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Comma-separated `@username`s from a user array, or `-` when empty.
+fn join_usernames(users: &Value) -> String {
+    let names: Vec<String> = users
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|u| u["username"].as_str())
+                .map(|u| format!("@{}", u))
+                .collect()
+        })
+        .unwrap_or_default();
+    if names.is_empty() {
+        "-".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+/// `long` adds columns with the assignees and reviewers of each MR.
+pub fn print_mrs(value: &Value, long: bool) {
     if let Some(mrs) = value.as_array() {
-        for mr in mrs {
-            let iid = mr["iid"].as_u64().unwrap_or(0);
-            let title = mr["title"].as_str().unwrap_or("");
-            let state = mr["state"].as_str().unwrap_or("");
-            let source = mr["source_branch"].as_str().unwrap_or("");
-            let target = mr["target_branch"].as_str().unwrap_or("");
-            let author = mr["author"]["username"].as_str().unwrap_or("");
+        let rows: Vec<Vec<String>> = mrs
+            .iter()
+            .map(|mr| {
+                let iid = mr["iid"].as_u64().unwrap_or(0);
+                let title = mr["title"].as_str().unwrap_or("");
+                let state = mr["state"].as_str().unwrap_or("");
+                let source = mr["source_branch"].as_str().unwrap_or("");
+                let target = mr["target_branch"].as_str().unwrap_or("");
+                let author = mr["author"]["username"].as_str().unwrap_or("");
+                let mut row = vec![
+                    format!("!{}", iid),
+                    title.to_string(),
+                    format!("[{}]", state),
+                    format!("{} -> {}", source, target),
+                    format!("@{}", author),
+                ];
+                if long {
+                    row.push(join_usernames(&mr["assignees"]));
+                    row.push(join_usernames(&mr["reviewers"]));
+                }
+                row
+            })
+            .collect();
+        if long {
+            let header = [
+                "MR",
+                "TITLE",
+                "STATE",
+                "BRANCHES",
+                "AUTHOR",
+                "ASSIGNEES",
+                "REVIEWERS",
+            ];
+            print_table(Some(&header), &rows);
+        } else {
+            print_table(None, &rows);
+        }
+    } else {
+        print_unexpected(value);
+    }
+}
+
+pub fn print_mr_detail(mr: &Value) {
+    if !mr.is_object() {
+        print_unexpected(mr);
+        return;
+    }
+    let iid = mr["iid"].as_u64().unwrap_or(0);
+    let title = mr["title"].as_str().unwrap_or("");
+    let state = mr["state"].as_str().unwrap_or("");
+    let source = mr["source_branch"].as_str().unwrap_or("");
+    let target = mr["target_branch"].as_str().unwrap_or("");
+    let author = mr["author"]["username"].as_str().unwrap_or("");
+    let merge_status = mr["detailed_merge_status"]
+        .as_str()
+        .or(mr["merge_status"].as_str())
+        .unwrap_or("");
+    let pipeline = mr["head_pipeline"]["status"].as_str().unwrap_or("none");
 
-            println!("!{:<5} {} [{}]", iid, title, state);
-            println!("       {} -> {} (@{})", source, target, author);
+    println!("!{} {} [{}]", iid, title, state);
+    println!("  {} -> {} (@{})", source, target, author);
+    println!("  merge status: {}", merge_status);
+    println!("  pipeline: {}", pipeline);
+    print_detail_labels(&mr["labels"]);
+    if mr["merge_when_pipeline_succeeds"]
+        .as_bool()
+        .unwrap_or(false)
+    {
+        match mr["merge_user"]["username"].as_str() {
+            Some(user) => println!("  auto-merge: enabled by @{}", user),
+            None => println!("  auto-merge: enabled"),
         }
+    } else {
+        println!("  auto-merge: off");
+    }
+    if let Some(url) = mr["web_url"].as_str() {
+        println!("  {}", url);
+    }
+    if let Some(description) = mr["description"].as_str().filter(|d| !d.trim().is_empty()) {
+        println!();
+        println!("{}", description.trim_end());
     }
 }
 
-pub fn print_issues(value: &Value) {
-    if let Some(issues) = value.as_array() {
-        for issue in issues {
-            let iid = issue["iid"].as_u64().unwrap_or(0);
-            let title = issue["title"].as_str().unwrap_or("");
-            let state = issue["state"].as_str().unwrap_or("");
-            let author = issue["author"]["username"].as_str().unwrap_or("");
-            let labels: Vec<&str> = issue["labels"]
+pub fn print_issue_detail(issue: &Value) {
+    if !issue.is_object() {
+        print_unexpected(issue);
+        return;
+    }
+    let iid = issue["iid"].as_u64().unwrap_or(0);
+    let title = issue["title"].as_str().unwrap_or("");
+    let state = issue["state"].as_str().unwrap_or("");
+    let author = issue["author"]["username"].as_str().unwrap_or("");
+    let assignees: Vec<String> = issue["assignees"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|a| a["username"].as_str())
+                .map(|u| format!("@{}", u))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    println!("#{} {} [{}]", iid, title, state);
+    println!("  author: @{}", author);
+    if !assignees.is_empty() {
+        println!("  assignees: {}", assignees.join(", "));
+    }
+    if let Some(milestone) = issue["milestone"]["title"].as_str() {
+        println!("  milestone: {}", milestone);
+    }
+    if let Some(weight) = issue["weight"].as_u64() {
+        println!("  weight: {}", weight);
+    }
+    if let Some(due) = issue["due_date"].as_str() {
+        println!("  due: {}", due);
+    }
+    print_detail_labels(&issue["labels"]);
+    if let Some(url) = issue["web_url"].as_str() {
+        println!("  {}", url);
+    }
+    if let Some(description) = issue["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+    {
+        println!();
+        println!("{}", description.trim_end());
+    }
+}
+
+/// Whether to emit ANSI colors: stdout (or the pager) is a terminal and
+/// `NO_COLOR` is unset.
+pub fn color_enabled() -> bool {
+    crate::pager::stdout_is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+/// Print a `labels:` line. Labels fetched with `with_labels_details=true` are
+/// objects and are drawn in their GitLab colors; plain string labels are
+/// printed as-is.
+fn print_detail_labels(labels: &Value) {
+    let Some(labels) = labels.as_array().filter(|l| !l.is_empty()) else {
+        return;
+    };
+    let color = color_enabled();
+    let rendered: Vec<String> = labels
+        .iter()
+        .map(|label| {
+            let name = label["name"].as_str().or(label.as_str()).unwrap_or("");
+            let background = label["color"].as_str().and_then(parse_hex_color);
+            match background {
+                Some((r, g, b)) if color => {
+                    let (fr, fg, fb) = label["text_color"]
+                        .as_str()
+                        .and_then(parse_hex_color)
+                        .unwrap_or((255, 255, 255));
+                    format!(
+                        "\x1b[48;2;{};{};{}m\x1b[38;2;{};{};{}m {} \x1b[0m",
+                        r, g, b, fr, fg, fb, name
+                    )
+                }
+                _ => name.to_string(),
+            }
+        })
+        .collect();
+    let separator = if color { " " } else { ", " };
+    println!("  labels: {}", rendered.join(separator));
+}
+
+/// Parse `#rrggbb` into its RGB components.
+fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+pub fn print_mr_versions(value: &Value) {
+    let Some(versions) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    let rows: Vec<Vec<String>> = versions
+        .iter()
+        .map(|version| {
+            let id = version["id"].as_u64().unwrap_or(0);
+            let created = version["created_at"].as_str().unwrap_or("");
+            let head = version["head_commit_sha"].as_str().unwrap_or("");
+            let state = version["state"].as_str().unwrap_or("");
+            vec![
+                id.to_string(),
+                created.get(..16).unwrap_or(created).replace('T', " "),
+                head.get(..8).unwrap_or(head).to_string(),
+                state.to_string(),
+            ]
+        })
+        .collect();
+    print_table(Some(&["VERSION", "CREATED", "HEAD", "STATE"]), &rows);
+}
+
+pub fn print_events(value: &Value) {
+    let Some(events) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    if events.is_empty() {
+        println!("No events found");
+        return;
+    }
+    let rows: Vec<Vec<String>> = events
+        .iter()
+        .map(|event| {
+            let created = event["created_at"].as_str().unwrap_or("");
+            let author = event["author"]["username"].as_str().unwrap_or("?");
+            let action = event["action_name"].as_str().unwrap_or("");
+            vec![
+                created.get(..16).unwrap_or(created).replace('T', " "),
+                format!("@{}", author),
+                action.to_string(),
+                event_target(event),
+            ]
+        })
+        .collect();
+    print_table(Some(&["WHEN", "WHO", "ACTION", "TARGET"]), &rows);
+}
+
+/// What an event acted on: the pushed ref, the commented item, or the
+/// MR/issue/etc. with its title.
+fn event_target(event: &Value) -> String {
+    let push = &event["push_data"];
+    if push.is_object() {
+        let ref_type = push["ref_type"].as_str().unwrap_or("branch");
+        let ref_name = push["ref"].as_str().unwrap_or("?");
+        let count = push["commit_count"].as_u64().unwrap_or(0);
+        return match push["commit_title"].as_str() {
+            Some(title) if count > 0 => {
+                format!("{} {} ({} commits): {}", ref_type, ref_name, count, title)
+            }
+            _ => format!("{} {}", ref_type, ref_name),
+        };
+    }
+    let note = &event["note"];
+    if note.is_object() {
+        let kind = note["noteable_type"].as_str().unwrap_or("");
+        let iid = note["noteable_iid"].as_u64();
+        return match iid {
+            Some(iid) => format!("{} {}{}", kind, iid_sigil(kind), iid),
+            None => kind.to_string(),
+        };
+    }
+    let kind = event["target_type"].as_str().unwrap_or("");
+    let title = event["target_title"].as_str().unwrap_or("");
+    match event["target_iid"].as_u64() {
+        Some(iid) => format!("{} {}{}: {}", kind, iid_sigil(kind), iid, title),
+        None if kind.is_empty() => title.to_string(),
+        None => format!("{} {}", kind, title),
+    }
+}
+
+fn iid_sigil(kind: &str) -> &'static str {
+    if kind == "MergeRequest" {
+        "!"
+    } else {
+        "#"
+    }
+}
+
+pub fn print_time_stats(stats: &Value) {
+    if !stats.is_object() {
+        print_unexpected(stats);
+        return;
+    }
+    let estimate = stats["human_time_estimate"].as_str().unwrap_or("-");
+    let spent = stats["human_total_time_spent"].as_str().unwrap_or("-");
+    println!("estimate: {}", estimate);
+    println!("spent:    {}", spent);
+    let estimate_secs = stats["time_estimate"].as_u64().unwrap_or(0);
+    let spent_secs = stats["total_time_spent"].as_u64().unwrap_or(0);
+    if let Some(percent) = (spent_secs * 100).checked_div(estimate_secs) {
+        println!("progress: {}%", percent);
+    }
+}
+
+pub fn print_approval_rules(value: &Value) {
+    if let Some(rules) = value.as_array() {
+        if rules.is_empty() {
+            println!("No approval rules");
+            return;
+        }
+        for rule in rules {
+            let name = rule["name"].as_str().unwrap_or("");
+            let required = rule["approvals_required"].as_u64().unwrap_or(0);
+            let approvers: Vec<String> = rule["eligible_approvers"]
                 .as_array()
-                .map(|arr| arr.iter().filter_map(|l| l.as_str()).collect())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|u| u["username"].as_str())
+                        .map(|u| format!("@{}", u))
+                        .collect()
+                })
                 .unwrap_or_default();
-
-            println!("#{:<5} {} [{}]", iid, title, state);
-            if labels.is_empty() {
-                println!("       @{}", author);
+            println!("{} (requires {})", name, required);
+            if approvers.is_empty() {
+                println!("  eligible: any member");
             } else {
-                println!("       @{} | {}", author, labels.join(", "));
+                println!("  eligible: {}", approvers.join(", "));
             }
         }
+    } else {
+        print_unexpected(value);
+    }
+}
+
+pub fn print_issues(value: &Value) {
+    if let Some(issues) = value.as_array() {
+        let rows: Vec<Vec<String>> = issues.iter().map(issue_row).collect();
+        print_table(None, &rows);
+    } else {
+        print_unexpected(value);
+    }
+}
+
+fn issue_row(issue: &Value) -> Vec<String> {
+    let iid = issue["iid"].as_u64().unwrap_or(0);
+    let title = issue["title"].as_str().unwrap_or("");
+    let state = issue["state"].as_str().unwrap_or("");
+    let author = issue["author"]["username"].as_str().unwrap_or("");
+    let labels: Vec<&str> = issue["labels"]
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|l| l.as_str()).collect())
+        .unwrap_or_default();
+
+    vec![
+        format!("#{}", iid),
+        title.to_string(),
+        format!("[{}]", state),
+        format!("@{}", author),
+        labels.join(", "),
+    ]
+}
+
+/// Issues bucketed under a header per label, assignee, or milestone. An issue
+/// with several labels or assignees appears in each of their sections; issues
+/// with none are listed last.
+pub fn print_issue_board(value: &Value, group_by: &str) {
+    let Some(issues) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    if issues.is_empty() {
+        println!("No issues found");
+        return;
+    }
+    let mut columns: BTreeMap<String, Vec<&Value>> = BTreeMap::new();
+    let mut rest = Vec::new();
+    for issue in issues {
+        let keys: Vec<String> = match group_by {
+            "assignee" => issue["assignees"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|user| user["username"].as_str())
+                .map(|username| format!("@{}", username))
+                .collect(),
+            "milestone" => issue["milestone"]["title"]
+                .as_str()
+                .map(String::from)
+                .into_iter()
+                .collect(),
+            _ => issue["labels"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|label| label.as_str())
+                .map(String::from)
+                .collect(),
+        };
+        if keys.is_empty() {
+            rest.push(issue);
+        }
+        for key in keys {
+            columns.entry(key).or_default().push(issue);
+        }
+    }
+    let none = match group_by {
+        "assignee" => "Unassigned",
+        "milestone" => "No milestone",
+        _ => "No label",
+    };
+    let bold = color_enabled();
+    let sections = columns
+        .iter()
+        .map(|(title, issues)| (title.as_str(), issues))
+        .chain((!rest.is_empty()).then_some((none, &rest)));
+    for (i, (title, issues)) in sections.enumerate() {
+        if i > 0 {
+            println!();
+        }
+        if bold {
+            println!("\x1b[1m{} ({})\x1b[0m", title, issues.len());
+        } else {
+            println!("{} ({})", title, issues.len());
+        }
+        let rows: Vec<Vec<String>> = issues.iter().map(|issue| issue_row(issue)).collect();
+        print_table(None, &rows);
+    }
+}
+
+pub fn print_issue_links(value: &Value) {
+    if let Some(links) = value.as_array() {
+        if links.is_empty() {
+            println!("No linked issues");
+            return;
+        }
+        for issue in links {
+            let link_type = issue["link_type"].as_str().unwrap_or("");
+            let reference = issue["references"]["full"]
+                .as_str()
+                .map(|r| r.to_string())
+                .unwrap_or_else(|| format!("#{}", issue["iid"].as_u64().unwrap_or(0)));
+            let title = issue["title"].as_str().unwrap_or("");
+            let state = issue["state"].as_str().unwrap_or("");
+            println!("{:<14} {} {} [{}]", link_type, reference, title, state);
+        }
+    } else {
+        print_unexpected(value);
     }
 }
 
@@ -49,24 +763,127 @@ fn access_level_name(level: u64) -> &'static str {
     }
 }
 
-pub fn print_group_members(value: &Value, show_email: bool) {
+pub fn print_discussion_thread(d: &Value) {
+    let disc_id = d["id"].as_str().unwrap_or("?");
+    let notes = d["notes"].as_array();
+    let first = notes.and_then(|n| n.first());
+
+    let position = first.and_then(|n| n["position"].as_object());
+    if let Some(pos) = position {
+        let path = pos
+            .get("new_path")
+            .or(pos.get("old_path"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("?");
+        let line = pos
+            .get("new_line")
+            .or(pos.get("old_line"))
+            .and_then(|v| v.as_u64())
+            .map(|l| l.to_string())
+            .unwrap_or_default();
+        println!("--- {} ({}:{})", disc_id, path, line);
+    } else {
+        println!("--- {}", disc_id);
+    }
+
+    if first
+        .and_then(|n| n["resolvable"].as_bool())
+        .unwrap_or(false)
+    {
+        let resolved = first.and_then(|n| n["resolved"].as_bool()).unwrap_or(false);
+        println!("  resolved: {}", resolved);
+    }
+
+    // The first note opens the thread; replies are indented beneath it
+    if let Some(notes_arr) = notes {
+        for (i, note) in notes_arr.iter().enumerate() {
+            let indent = if i == 0 { "  " } else { "    " };
+            let author = note["author"]["username"].as_str().unwrap_or("?");
+            let tag = if note["system"].as_bool().unwrap_or(false) {
+                " [system]"
+            } else {
+                ""
+            };
+            let body = note["body"].as_str().unwrap_or("");
+            let mut lines = body.lines();
+            println!(
+                "{}@{}{}: {}",
+                indent,
+                author,
+                tag,
+                lines.next().unwrap_or("")
+            );
+            for line in lines {
+                println!("{}    {}", indent, line);
+            }
+        }
+    }
+    println!();
+}
+
+pub fn print_group_members(value: &Value, show_email: bool, show_inherited: bool) {
     if let Some(members) = value.as_array() {
         if members.is_empty() {
             println!("No members found");
             return;
         }
-        for member in members {
-            let username = member["username"].as_str().unwrap_or("");
-            let name = member["name"].as_str().unwrap_or("");
-            let access_level = member["access_level"].as_u64().unwrap_or(0);
-            let access = access_level_name(access_level);
-            if show_email {
-                let email = member["email"].as_str().unwrap_or("-");
-                println!("{:<25} {:<12} {:<35} {}", username, access, email, name);
-            } else {
-                println!("{:<25} {:<12} {}", username, access, name);
-            }
-        }
+        let rows: Vec<Vec<String>> = members
+            .iter()
+            .map(|member| {
+                let username = member["username"].as_str().unwrap_or("");
+                let name = member["name"].as_str().unwrap_or("");
+                let access_level = member["access_level"].as_u64().unwrap_or(0);
+                let access = access_level_name(access_level);
+                let mut row = vec![username.to_string(), access.to_string()];
+                if show_email {
+                    row.push(member["email"].as_str().unwrap_or("-").to_string());
+                } else if show_inherited {
+                    let source = if member["inherited"].as_bool().unwrap_or(false) {
+                        "inherited"
+                    } else {
+                        "direct"
+                    };
+                    row.push(source.to_string());
+                }
+                row.push(name.to_string());
+                row
+            })
+            .collect();
+        print_table(None, &rows);
+    } else {
+        print_unexpected(value);
+    }
+}
+
+pub fn print_group_detail(group: &Value) {
+    if !group.is_object() {
+        print_unexpected(group);
+        return;
+    }
+    let path = group["full_path"].as_str().unwrap_or("");
+    let name = group["name"].as_str().unwrap_or("");
+    let visibility = group["visibility"].as_str().unwrap_or("");
+    let count = |key: &str| {
+        group[key]
+            .as_u64()
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "?".to_string())
+    };
+
+    println!("{} ({})", path, name);
+    println!("  visibility: {}", visibility);
+    println!("  projects: {}", count("project_count"));
+    println!("  members: {}", count("member_count"));
+    println!("  subgroups: {}", count("subgroup_count"));
+    if let Some(url) = group["web_url"].as_str() {
+        println!("  {}", url);
+    }
+    if let Some(description) = group["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+    {
+        println!();
+        println!("{}", description.trim_end());
     }
 }
 
@@ -76,33 +893,74 @@ pub fn print_subgroups(value: &Value) {
             println!("No subgroups found");
             return;
         }
-        for group in groups {
-            let path = group["full_path"].as_str().unwrap_or("");
-            let name = group["name"].as_str().unwrap_or("");
-            let visibility = group["visibility"].as_str().unwrap_or("");
-            println!("{:<40} {:<10} {}", path, visibility, name);
-        }
+        let rows: Vec<Vec<String>> = groups
+            .iter()
+            .map(|group| {
+                ["full_path", "visibility", "name"]
+                    .iter()
+                    .map(|key| group[*key].as_str().unwrap_or("").to_string())
+                    .collect()
+            })
+            .collect();
+        print_table(None, &rows);
+    } else {
+        print_unexpected(value);
     }
 }
 
-pub fn print_projects(value: &Value) {
+/// `with_stats` adds last activity and storage size columns, from a listing
+/// fetched with `statistics=true`.
+pub fn print_projects(value: &Value, with_stats: bool) {
     if let Some(projects) = value.as_array() {
         if projects.is_empty() {
             println!("No projects found");
             return;
         }
-        for project in projects {
-            let path = project["path_with_namespace"].as_str().unwrap_or("");
-            let visibility = project["visibility"].as_str().unwrap_or("");
-            let archived = project["archived"].as_bool().unwrap_or(false);
-            let default_branch = project["default_branch"].as_str().unwrap_or("-");
-            let status = if archived { "[archived]" } else { "" };
-            println!(
-                "{:<45} {:<10} {:<10} {}",
-                path, visibility, default_branch, status
-            );
-        }
+        let rows: Vec<Vec<String>> = projects
+            .iter()
+            .map(|project| {
+                let path = project["path_with_namespace"].as_str().unwrap_or("");
+                let visibility = project["visibility"].as_str().unwrap_or("");
+                let archived = project["archived"].as_bool().unwrap_or(false);
+                let default_branch = project["default_branch"].as_str().unwrap_or("-");
+                let status = if archived { "[archived]" } else { "" };
+                let mut row = vec![
+                    path.to_string(),
+                    visibility.to_string(),
+                    default_branch.to_string(),
+                ];
+                if with_stats {
+                    let activity = project["last_activity_at"].as_str().unwrap_or("-");
+                    // Statistics are omitted for projects the token can't maintain
+                    let size = project["statistics"]["storage_size"]
+                        .as_u64()
+                        .map_or_else(|| "-".to_string(), format_size);
+                    row.push(activity.get(..10).unwrap_or(activity).to_string());
+                    row.push(size);
+                }
+                row.push(status.to_string());
+                row
+            })
+            .collect();
+        print_table(None, &rows);
+    } else {
+        print_unexpected(value);
+    }
+}
+
+/// Bytes in binary units: `512 B`, `1.5 KiB`, `2.0 GiB`.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
     }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
 }
 
 pub fn print_mirrors(value: &Value) {
@@ -114,6 +972,8 @@ pub fn print_mirrors(value: &Value) {
         for mirror in mirrors {
             print_mirror(mirror);
         }
+    } else {
+        print_unexpected(value);
     }
 }
 
@@ -149,6 +1009,70 @@ fn print_mirror(mirror: &Value) {
     }
 }
 
+pub fn print_runners(value: &Value) {
+    if let Some(runners) = value.as_array() {
+        if runners.is_empty() {
+            println!("No runners found");
+            return;
+        }
+        let rows: Vec<Vec<String>> = runners
+            .iter()
+            .map(|runner| {
+                let id = runner["id"].as_u64().unwrap_or(0);
+                let description = runner["description"].as_str().unwrap_or("");
+                let status = runner["status"].as_str().unwrap_or("");
+                let tags: Vec<&str> = runner["tag_list"]
+                    .as_array()
+                    .map(|arr| arr.iter().filter_map(|t| t.as_str()).collect())
+                    .unwrap_or_default();
+                vec![
+                    format!("#{}", id),
+                    status.to_string(),
+                    description.to_string(),
+                    tags.join(", "),
+                ]
+            })
+            .collect();
+        print_table(Some(&["ID", "STATUS", "DESCRIPTION", "TAGS"]), &rows);
+    } else {
+        print_unexpected(value);
+    }
+}
+
+pub fn print_wiki_pages(value: &Value) {
+    let Some(pages) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    if pages.is_empty() {
+        println!("No wiki pages");
+        return;
+    }
+    let rows: Vec<Vec<String>> = pages
+        .iter()
+        .map(|page| {
+            ["title", "slug", "format"]
+                .iter()
+                .map(|key| page[*key].as_str().unwrap_or("").to_string())
+                .collect()
+        })
+        .collect();
+    print_table(Some(&["TITLE", "SLUG", "FORMAT"]), &rows);
+}
+
+pub fn print_wiki_page(page: &Value) {
+    if !page.is_object() {
+        print_unexpected(page);
+        return;
+    }
+    let title = page["title"].as_str().unwrap_or("");
+    let format = page["format"].as_str().unwrap_or("");
+    let content = page["content"].as_str().unwrap_or("");
+    println!("# {} [{}]", title, format);
+    println!();
+    println!("{}", content.trim_end());
+}
+
 pub fn print_webhooks(value: &Value) {
     if let Some(hooks) = value.as_array() {
         if hooks.is_empty() {
@@ -158,6 +1082,8 @@ pub fn print_webhooks(value: &Value) {
         for hook in hooks {
             print_webhook(hook);
         }
+    } else {
+        print_unexpected(value);
     }
 }
 
@@ -199,21 +1125,143 @@ fn print_webhook(hook: &Value) {
     }
 }
 
+pub fn print_ci_lint(result: &Value) {
+    if !result.is_object() {
+        print_unexpected(result);
+        return;
+    }
+    let valid = result["valid"].as_bool().unwrap_or(false);
+    println!("{}", if valid { "valid" } else { "invalid" });
+    for (kind, key) in [("error", "errors"), ("warning", "warnings")] {
+        if let Some(messages) = result[key].as_array() {
+            for message in messages {
+                println!("  {}: {}", kind, message.as_str().unwrap_or(""));
+            }
+        }
+    }
+}
+
+pub fn print_pipelines(value: &Value) {
+    let Some(pipelines) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    if pipelines.is_empty() {
+        println!("No pipelines");
+        return;
+    }
+    let rows: Vec<Vec<String>> = pipelines
+        .iter()
+        .map(|pipeline| {
+            let id = pipeline["id"].as_u64().unwrap_or(0);
+            let status = pipeline["status"].as_str().unwrap_or("");
+            let ref_name = pipeline["ref"].as_str().unwrap_or("");
+            let sha = pipeline["sha"].as_str().unwrap_or("");
+            let updated = pipeline["updated_at"].as_str().unwrap_or("");
+            vec![
+                id.to_string(),
+                status.to_string(),
+                ref_name.to_string(),
+                sha.get(..8).unwrap_or(sha).to_string(),
+                updated.get(..16).unwrap_or(updated).replace('T', " "),
+            ]
+        })
+        .collect();
+    print_table(Some(&["ID", "STATUS", "REF", "SHA", "UPDATED"]), &rows);
+}
+
 pub fn print_ci_variables(value: &Value) {
     if let Some(vars) = value.as_array() {
         if vars.is_empty() {
             println!("No CI/CD variables found");
             return;
         }
-        println!("{:<40} {:<10} {:<8} {}", "KEY", "PROTECTED", "MASKED", "ENVIRONMENT");
-        println!("{}", "-".repeat(80));
-        for var in vars {
-            let key = var["key"].as_str().unwrap_or("");
-            let protected = if var["protected"].as_bool().unwrap_or(false) { "yes" } else { "no" };
-            let masked = if var["masked"].as_bool().unwrap_or(false) { "yes" } else { "no" };
-            let env_scope = var["environment_scope"].as_str().unwrap_or("*");
-            println!("{:<40} {:<10} {:<8} {}", key, protected, masked, env_scope);
+        let rows: Vec<Vec<String>> = vars
+            .iter()
+            .map(|var| {
+                let key = var["key"].as_str().unwrap_or("");
+                let protected = if var["protected"].as_bool().unwrap_or(false) {
+                    "yes"
+                } else {
+                    "no"
+                };
+                let masked = if var["masked"].as_bool().unwrap_or(false) {
+                    "yes"
+                } else {
+                    "no"
+                };
+                let env_scope = var["environment_scope"].as_str().unwrap_or("*");
+                vec![
+                    key.to_string(),
+                    protected.to_string(),
+                    masked.to_string(),
+                    env_scope.to_string(),
+                ]
+            })
+            .collect();
+        print_table(Some(&["KEY", "PROTECTED", "MASKED", "ENVIRONMENT"]), &rows);
+    } else {
+        print_unexpected(value);
+    }
+}
+
+pub fn print_access_tokens(value: &Value) {
+    if let Some(tokens) = value.as_array() {
+        if tokens.is_empty() {
+            println!("No access tokens found");
+            return;
         }
+        let today = chrono::Utc::now().date_naive();
+        let mut rows = Vec::new();
+        for token in tokens {
+            let id = token["id"].as_u64().unwrap_or(0);
+            let name = token["name"].as_str().unwrap_or("");
+            let scopes: Vec<&str> = token["scopes"]
+                .as_array()
+                .map(|arr| arr.iter().filter_map(|s| s.as_str()).collect())
+                .unwrap_or_default();
+            let expires_at = token["expires_at"].as_str();
+            let expired = expires_at
+                .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                .is_some_and(|d| d <= today);
+            let status = if token["revoked"].as_bool().unwrap_or(false) {
+                "[revoked]"
+            } else if expired {
+                "[expired]"
+            } else {
+                ""
+            };
+            rows.push(vec![
+                id.to_string(),
+                name.to_string(),
+                expires_at.unwrap_or("never").to_string(),
+                scopes.join(","),
+                status.to_string(),
+            ]);
+        }
+        print_table(None, &rows);
+    } else {
+        print_unexpected(value);
+    }
+}
+
+pub fn print_commit_statuses(value: &Value) {
+    if let Some(statuses) = value.as_array() {
+        if statuses.is_empty() {
+            println!("No commit statuses");
+            return;
+        }
+        for status in statuses {
+            let name = status["name"].as_str().unwrap_or("");
+            let state = status["status"].as_str().unwrap_or("");
+            let description = status["description"].as_str().unwrap_or("");
+            println!("{:<10} {} {}", state, name, description);
+            if let Some(url) = status["target_url"].as_str() {
+                println!("           {}", url);
+            }
+        }
+    } else {
+        print_unexpected(value);
     }
 }
 
@@ -233,5 +1281,364 @@ pub fn print_protected_branches(value: &Value) {
             };
             println!("{} {}", name, force_push_str);
         }
+    } else {
+        print_unexpected(value);
+    }
+}
+
+pub fn print_commits(value: &Value) {
+    if let Some(commits) = value.as_array() {
+        for commit in commits {
+            let short_id = commit["short_id"].as_str().unwrap_or("");
+            let title = commit["title"].as_str().unwrap_or("");
+            let author = commit["author_name"].as_str().unwrap_or("");
+            let date = commit["committed_date"].as_str().unwrap_or("");
+            let date = date.get(..10).unwrap_or(date);
+            println!("{} {} {} ({})", short_id, date, title, author);
+        }
+    } else {
+        print_unexpected(value);
+    }
+}
+
+/// Markdown bullets for release notes: `- Title (!12, @author)`.
+pub fn print_changelog_mrs(value: &Value) {
+    let Some(mrs) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    for mr in mrs {
+        let title = mr["title"].as_str().unwrap_or("");
+        let iid = mr["iid"].as_u64().unwrap_or(0);
+        let author = mr["author"]["username"].as_str().unwrap_or("");
+        println!("- {} (!{}, @{})", title, iid, author);
+    }
+}
+
+/// Markdown bullets for release notes: `- Title (abc1234, Author Name)`.
+pub fn print_changelog_commits(value: &Value) {
+    let Some(commits) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    for commit in commits {
+        let title = commit["title"].as_str().unwrap_or("");
+        let short_id = commit["short_id"].as_str().unwrap_or("");
+        let author = commit["author_name"].as_str().unwrap_or("");
+        println!("- {} ({}, {})", title, short_id, author);
+    }
+}
+
+/// Render a unified diff with word-level changes: each run of removed lines
+/// and the added lines that follow it are merged, with removed words shown as
+/// `[-word-]` and added words as `{+word+}` (red/green when `color` is set).
+pub fn print_word_diff(diff: &str, color: bool) {
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix('-') {
+            if !added.is_empty() {
+                flush_word_diff(&mut removed, &mut added, color);
+            }
+            removed.push(rest);
+        } else if let Some(rest) = line.strip_prefix('+') {
+            added.push(rest);
+        } else {
+            flush_word_diff(&mut removed, &mut added, color);
+            match line.strip_prefix(' ') {
+                Some(context) => println!("{}", context),
+                None => println!("{}", line),
+            }
+        }
+    }
+    flush_word_diff(&mut removed, &mut added, color);
+}
+
+/// Beyond this many token comparisons a block is shown line-by-line instead.
+const WORD_DIFF_MAX_CELLS: usize = 4_000_000;
+
+fn flush_word_diff(removed: &mut Vec<&str>, added: &mut Vec<&str>, color: bool) {
+    if removed.is_empty() && added.is_empty() {
+        return;
+    }
+    let old = removed.join("\n");
+    let new = added.join("\n");
+    removed.clear();
+    added.clear();
+
+    let old_tokens = tokenize_words(&old);
+    let new_tokens = tokenize_words(&new);
+    let mut out = String::new();
+    if old_tokens.len().saturating_mul(new_tokens.len()) > WORD_DIFF_MAX_CELLS {
+        push_word_change(&mut out, &old, false, color);
+        out.push('\n');
+        push_word_change(&mut out, &new, true, color);
+    } else {
+        for (op, token) in diff_tokens(&old_tokens, &new_tokens) {
+            match op {
+                WordOp::Same => out.push_str(token),
+                WordOp::Removed => push_word_change(&mut out, token, false, color),
+                WordOp::Added => push_word_change(&mut out, token, true, color),
+            }
+        }
+    }
+    println!("{}", out);
+}
+
+fn push_word_change(out: &mut String, text: &str, added: bool, color: bool) {
+    // Markers never span lines, so each output line stays self-contained
+    for (i, part) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        if part.is_empty() {
+            continue;
+        }
+        match (added, color) {
+            (false, true) => out.push_str(&format!("\x1b[31m{}\x1b[0m", part)),
+            (true, true) => out.push_str(&format!("\x1b[32m{}\x1b[0m", part)),
+            (false, false) => out.push_str(&format!("[-{}-]", part)),
+            (true, false) => out.push_str(&format!("{{+{}+}}", part)),
+        }
+    }
+}
+
+/// Split into words, whitespace runs, and single punctuation characters.
+fn tokenize_words(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let class = |c: char| {
+            if c.is_alphanumeric() || c == '_' {
+                0
+            } else if c == '\n' {
+                1
+            } else if c.is_whitespace() {
+                2
+            } else {
+                3
+            }
+        };
+        let current = class(c);
+        let continues = match chars.peek() {
+            Some(&(_, next)) => current != 1 && current != 3 && class(next) == current,
+            None => false,
+        };
+        if !continues {
+            let end = i + c.len_utf8();
+            tokens.push(&text[start..end]);
+            start = end;
+        }
+    }
+    tokens
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum WordOp {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Longest-common-subsequence diff over tokens.
+fn diff_tokens<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(WordOp, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            ops.push((WordOp::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push((WordOp::Removed, old[i]));
+            i += 1;
+        } else {
+            ops.push((WordOp::Added, new[j]));
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// Changed paths one per line, like `git diff --name-only`; `with_status`
+/// prefixes each with A/M/D/R and shows both paths of a rename.
+pub fn print_diff_names(value: &Value, with_status: bool) {
+    let Some(changes) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    for change in changes {
+        let new_path = change["new_path"].as_str().unwrap_or("");
+        if !with_status {
+            println!("{}", new_path);
+            continue;
+        }
+        let flag = |key: &str| change[key].as_bool().unwrap_or(false);
+        if flag("renamed_file") {
+            let old_path = change["old_path"].as_str().unwrap_or("");
+            println!("R\t{}\t{}", old_path, new_path);
+        } else {
+            let status = if flag("new_file") {
+                "A"
+            } else if flag("deleted_file") {
+                "D"
+            } else {
+                "M"
+            };
+            println!("{}\t{}", status, new_path);
+        }
+    }
+}
+
+/// Print a `git diff --stat` style summary for an array of GitLab diff objects.
+pub fn print_diff_stat(value: &Value) {
+    let Some(diffs) = value.as_array() else {
+        print_unexpected(value);
+        return;
+    };
+    let mut total_added = 0;
+    let mut total_removed = 0;
+    for diff in diffs {
+        let path = diff["new_path"].as_str().unwrap_or("");
+        let (added, removed) = count_diff_lines(diff["diff"].as_str().unwrap_or(""));
+        total_added += added;
+        total_removed += removed;
+        println!(
+            " {:<50} | {:>5} {}{}",
+            path,
+            added + removed,
+            "+".repeat(added.min(30)),
+            "-".repeat(removed.min(30))
+        );
+    }
+    println!(
+        " {} files changed, {} insertions(+), {} deletions(-)",
+        diffs.len(),
+        total_added,
+        total_removed
+    );
+}
+
+fn count_diff_lines(diff: &str) -> (usize, usize) {
+    let mut added = 0;
+    let mut removed = 0;
+    for line in diff.lines() {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            added += 1;
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            removed += 1;
+        }
+    }
+    (added, removed)
+}
+
+/// Fallback for list printers when the API returns something other than an
+/// array: surface error messages, otherwise show the raw object.
+fn print_unexpected(value: &Value) {
+    if let Some(msg) = value.get("message").or_else(|| value.get("error")) {
+        match msg.as_str() {
+            Some(s) => eprintln!("Error: {}", s),
+            None => eprintln!("Error: {}", msg),
+        }
+    } else if value.as_object().is_some_and(|o| !o.is_empty()) {
+        print_json(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_diff(old: &str, new: &str) -> Vec<(WordOp, String)> {
+        let (old, new) = (tokenize_words(old), tokenize_words(new));
+        diff_tokens(&old, &new)
+            .into_iter()
+            .map(|(op, token)| (op, token.to_string()))
+            .collect()
+    }
+
+    fn ops(expected: &[(WordOp, &str)]) -> Vec<(WordOp, String)> {
+        expected
+            .iter()
+            .map(|&(op, token)| (op, token.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_words_splits_words_spaces_and_punctuation() {
+        assert_eq!(
+            tokenize_words("let x_1 =  f(a);\n"),
+            vec!["let", " ", "x_1", " ", "=", "  ", "f", "(", "a", ")", ";", "\n"]
+        );
+        assert!(tokenize_words("").is_empty());
+    }
+
+    #[test]
+    fn diff_tokens_marks_an_inserted_run() {
+        use WordOp::*;
+        assert_eq!(
+            word_diff("a c", "a b c"),
+            ops(&[
+                (Same, "a"),
+                (Same, " "),
+                (Added, "b"),
+                (Added, " "),
+                (Same, "c")
+            ])
+        );
+    }
+
+    #[test]
+    fn diff_tokens_marks_a_deleted_run() {
+        use WordOp::*;
+        assert_eq!(
+            word_diff("a b c", "a c"),
+            ops(&[
+                (Same, "a"),
+                (Same, " "),
+                (Removed, "b"),
+                (Removed, " "),
+                (Same, "c")
+            ])
+        );
+    }
+
+    #[test]
+    fn diff_tokens_marks_a_replacement_as_removed_then_added() {
+        use WordOp::*;
+        assert_eq!(
+            word_diff("let x = 1;", "let x = 2;"),
+            ops(&[
+                (Same, "let"),
+                (Same, " "),
+                (Same, "x"),
+                (Same, " "),
+                (Same, "="),
+                (Same, " "),
+                (Removed, "1"),
+                (Added, "2"),
+                (Same, ";"),
+            ])
+        );
+    }
+
+    #[test]
+    fn diff_tokens_handles_empty_sides() {
+        use WordOp::*;
+        assert_eq!(word_diff("", "new"), ops(&[(Added, "new")]));
+        assert_eq!(word_diff("old", ""), ops(&[(Removed, "old")]));
     }
 }
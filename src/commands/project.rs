@@ -1,7 +1,10 @@
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 
+use crate::api::ApiError;
+use crate::cache;
 use crate::cli::{MirrorCommands, ProjectCommands};
-use crate::commands::print::{print_mirrors, print_projects};
+use crate::commands::print::{print_mirrors, print_output, print_page, print_projects, with_meta};
+use crate::commands::{api_message, confirm, not_found};
 use crate::config::Config;
 use crate::get_group_client;
 
@@ -9,7 +12,16 @@ pub async fn handle(config: &mut Config, command: ProjectCommands) -> Result<()>
     match command {
         ProjectCommands::Archive { project } => handle_archive(config, &project).await,
         ProjectCommands::Unarchive { project } => handle_unarchive(config, &project).await,
-        ProjectCommands::List { group, archived, per_page } => handle_list(config, &group, per_page, archived).await,
+        ProjectCommands::Delete { project, yes } => handle_delete(config, &project, yes).await,
+        ProjectCommands::Transfer { project, to, yes } => {
+            handle_transfer(config, &project, &to, yes).await
+        }
+        ProjectCommands::List {
+            group,
+            archived,
+            with_stats,
+            per_page,
+        } => handle_list(config, &group, per_page, archived, with_stats).await,
         ProjectCommands::Update {
             project,
             repository_access_level,
@@ -47,7 +59,7 @@ async fn handle_archive(config: &mut Config, project: &str) -> Result<()> {
     let client = get_group_client(config).await?;
     let result = client.archive_project(project).await?;
     let name = result["path_with_namespace"].as_str().unwrap_or(project);
-    println!("Archived: {}", name);
+    success!("Archived: {}", name);
     Ok(())
 }
 
@@ -55,19 +67,86 @@ async fn handle_unarchive(config: &mut Config, project: &str) -> Result<()> {
     let client = get_group_client(config).await?;
     let result = client.unarchive_project(project).await?;
     let name = result["path_with_namespace"].as_str().unwrap_or(project);
-    println!("Unarchived: {}", name);
+    success!("Unarchived: {}", name);
+    Ok(())
+}
+
+async fn handle_delete(config: &mut Config, project: &str, yes: bool) -> Result<()> {
+    if !yes && !confirm(&format!("Delete project {}", project), Some(project))? {
+        bail!("Aborted: project path did not match");
+    }
+    let client = get_group_client(config).await?;
+    let result = client.delete_project(project).await?;
+    let message = result["message"].as_str().unwrap_or("accepted");
+    success!("Deletion of {} requested: {}", project, message);
+    Ok(())
+}
+
+async fn handle_transfer(config: &mut Config, project: &str, to: &str, yes: bool) -> Result<()> {
+    let prompt = format!("Transfer {} to {} (its URL will change)", project, to);
+    if !yes && !confirm(&prompt, Some(project))? {
+        bail!("Aborted: project path did not match");
+    }
+    let client = get_group_client(config).await?;
+    let group = client
+        .get_group(to)
+        .await
+        .map_err(not_found(|| format!("Group {} not found", to)))?;
+    let Some(namespace) = group["id"].as_u64() else {
+        bail!("Group {} has no ID in the API response", to);
+    };
+    let result = client
+        .transfer_project(project, namespace)
+        .await
+        .map_err(|err| match err.downcast_ref::<ApiError>() {
+            Some(api_err) if api_err.is_bad_request() => anyhow!(
+                "Cannot transfer {} to {}: {}",
+                project,
+                to,
+                api_message(&api_err.body)
+            ),
+            _ => err,
+        })?;
+    let name = result["path_with_namespace"].as_str().unwrap_or(project);
+    success!("Transferred {} to {}", project, name);
     Ok(())
 }
 
 async fn handle_list(
     config: &mut Config,
     group: &str,
-    per_page: u32,
+    per_page: Option<u32>,
     archived: bool,
+    with_stats: bool,
 ) -> Result<()> {
-    let client = get_group_client(config).await?;
-    let result = client.list_group_projects(group, per_page, archived).await?;
-    print_projects(&result);
+    // The cache holds the whole list without statistics or pagination headers,
+    // so --with-stats, --per-page and --with-meta always ask the API
+    let ttl = match config.group_cache_ttl {
+        Some(ttl) if !with_stats && per_page.is_none() && !with_meta() => ttl,
+        _ => {
+            let client = get_group_client(config).await?;
+            let (result, headers) = client
+                .list_group_projects(group, per_page.unwrap_or(50), archived, with_stats)
+                .await?;
+            print_page(&result, &headers, |value| print_projects(value, with_stats));
+            return Ok(());
+        }
+    };
+
+    let dir = config.cache_dir();
+    let host = config.host().to_string();
+    let projects = match cache::load_group_projects(&dir, &host, group, archived, ttl) {
+        Some(projects) => projects,
+        None => {
+            let client = get_group_client(config).await?;
+            let projects = client.list_all_group_projects(group, archived).await?;
+            if let Err(e) = cache::store_group_projects(&dir, &host, group, archived, &projects) {
+                eprintln!("Warning: {:#}", e);
+            }
+            projects
+        }
+    };
+    print_output(&projects, |value| print_projects(value, false));
     Ok(())
 }
 
@@ -81,7 +160,11 @@ fn insert_access_level(
             "enabled" | "private" | "disabled" => {
                 body.insert(key.to_string(), serde_json::Value::String(v));
             }
-            _ => bail!("Invalid value for {}: '{}' (expected: enabled, private, disabled)", key, v),
+            _ => bail!(
+                "Invalid value for {}: '{}' (expected: enabled, private, disabled)",
+                key,
+                v
+            ),
         }
     }
     Ok(())
@@ -103,9 +186,17 @@ fn build_update_body(
 ) -> Result<serde_json::Value> {
     let mut body = serde_json::Map::new();
 
-    insert_access_level(&mut body, "repository_access_level", repository_access_level)?;
+    insert_access_level(
+        &mut body,
+        "repository_access_level",
+        repository_access_level,
+    )?;
     insert_access_level(&mut body, "issues_access_level", issues_access_level)?;
-    insert_access_level(&mut body, "merge_requests_access_level", merge_requests_access_level)?;
+    insert_access_level(
+        &mut body,
+        "merge_requests_access_level",
+        merge_requests_access_level,
+    )?;
     insert_access_level(&mut body, "builds_access_level", builds_access_level)?;
     insert_access_level(&mut body, "wiki_access_level", wiki_access_level)?;
     insert_access_level(&mut body, "snippets_access_level", snippets_access_level)?;
@@ -125,7 +216,10 @@ fn build_update_body(
             "private" | "internal" | "public" => {
                 body.insert("visibility".to_string(), serde_json::Value::String(v));
             }
-            _ => bail!("Invalid visibility: '{}' (expected: private, internal, public)", v),
+            _ => bail!(
+                "Invalid visibility: '{}' (expected: private, internal, public)",
+                v
+            ),
         }
     }
 
@@ -136,17 +230,11 @@ fn build_update_body(
     Ok(serde_json::Value::Object(body))
 }
 
-async fn handle_update(
-    config: &mut Config,
-    project: &str,
-    body: &serde_json::Value,
-) -> Result<()> {
+async fn handle_update(config: &mut Config, project: &str, body: &serde_json::Value) -> Result<()> {
     let client = get_group_client(config).await?;
     let result = client.update_project(project, body).await?;
-    let name = result["path_with_namespace"]
-        .as_str()
-        .unwrap_or(project);
-    println!("Updated: {}", name);
+    let name = result["path_with_namespace"].as_str().unwrap_or(project);
+    success!("Updated: {}", name);
     print_updated_fields(body);
     Ok(())
 }
@@ -156,9 +244,9 @@ fn print_updated_fields(body: &serde_json::Value) {
         for (key, value) in obj {
             let display = key.replace('_', " ");
             match value {
-                serde_json::Value::String(s) => println!("  {} = {}", display, s),
-                serde_json::Value::Bool(b) => println!("  {} = {}", display, b),
-                _ => println!("  {} = {}", display, value),
+                serde_json::Value::String(s) => success!("  {} = {}", display, s),
+                serde_json::Value::Bool(b) => success!("  {} = {}", display, b),
+                _ => success!("  {} = {}", display, value),
             }
         }
     }
@@ -167,17 +255,36 @@ fn print_updated_fields(body: &serde_json::Value) {
 async fn handle_mirrors(config: &mut Config, command: MirrorCommands) -> Result<()> {
     match command {
         MirrorCommands::List { project } => handle_mirror_list(config, &project).await,
-        MirrorCommands::Create { project, url, only_protected } => handle_mirror_create(config, &project, &url, only_protected).await,
-        MirrorCommands::CreateHttps { project, url, user, password, only_protected } => handle_mirror_create_https(config, &project, &url, &user, &password, only_protected).await,
-        MirrorCommands::Remove { project, mirror_id } => handle_mirror_remove(config, &project, mirror_id).await,
-        MirrorCommands::Sync { project, mirror_id } => handle_mirror_sync(config, &project, mirror_id).await,
+        MirrorCommands::Create {
+            project,
+            url,
+            only_protected,
+        } => handle_mirror_create(config, &project, &url, only_protected).await,
+        MirrorCommands::CreateHttps {
+            project,
+            url,
+            user,
+            password,
+            only_protected,
+        } => {
+            handle_mirror_create_https(config, &project, &url, &user, &password, only_protected)
+                .await
+        }
+        MirrorCommands::Remove {
+            project,
+            mirror_id,
+            yes,
+        } => handle_mirror_remove(config, &project, mirror_id, yes).await,
+        MirrorCommands::Sync { project, mirror_id } => {
+            handle_mirror_sync(config, &project, mirror_id).await
+        }
     }
 }
 
 async fn handle_mirror_list(config: &mut Config, project: &str) -> Result<()> {
     let client = get_group_client(config).await?;
     let result = client.list_push_mirrors(project).await?;
-    print_mirrors(&result);
+    print_output(&result, print_mirrors);
     Ok(())
 }
 
@@ -188,10 +295,12 @@ async fn handle_mirror_create(
     only_protected: bool,
 ) -> Result<()> {
     let client = get_group_client(config).await?;
-    let result = client.create_push_mirror(project, url, true, only_protected).await?;
+    let result = client
+        .create_push_mirror(project, url, true, only_protected)
+        .await?;
     let id = result["id"].as_u64().unwrap_or(0);
     let mirror_url = result["url"].as_str().unwrap_or(url);
-    println!("Created push mirror (id: {}) -> {}", id, mirror_url);
+    success!("Created push mirror (id: {}) -> {}", id, mirror_url);
 
     if let Ok(ssh_key) = client.get_push_mirror_public_key(project, id).await {
         if !ssh_key.is_empty() {
@@ -200,9 +309,7 @@ async fn handle_mirror_create(
         }
     }
 
-    println!(
-        "\nNOTE: Go to GitLab UI and click 'Detect host keys' to complete setup:"
-    );
+    println!("\nNOTE: Go to GitLab UI and click 'Detect host keys' to complete setup:");
     println!(
         "https://gitlab.com/{}/-/settings/repository#js-push-remote-settings",
         project
@@ -224,20 +331,33 @@ async fn handle_mirror_create_https(
         .await?;
     let id = result["id"].as_u64().unwrap_or(0);
     let mirror_url = result["url"].as_str().unwrap_or(url);
-    println!("Created HTTPS push mirror (id: {}) -> {}", id, mirror_url);
+    success!("Created HTTPS push mirror (id: {}) -> {}", id, mirror_url);
     Ok(())
 }
 
-async fn handle_mirror_remove(config: &mut Config, project: &str, mirror_id: u64) -> Result<()> {
+async fn handle_mirror_remove(
+    config: &mut Config,
+    project: &str,
+    mirror_id: u64,
+    yes: bool,
+) -> Result<()> {
+    if !yes
+        && !confirm(
+            &format!("Remove push mirror {} from {}", mirror_id, project),
+            None,
+        )?
+    {
+        bail!("Aborted");
+    }
     let client = get_group_client(config).await?;
     client.delete_push_mirror(project, mirror_id).await?;
-    println!("Removed mirror {}", mirror_id);
+    success!("Removed mirror {}", mirror_id);
     Ok(())
 }
 
 async fn handle_mirror_sync(config: &mut Config, project: &str, mirror_id: u64) -> Result<()> {
     let client = get_group_client(config).await?;
     client.sync_push_mirror(project, mirror_id).await?;
-    println!("Triggered sync for mirror {}", mirror_id);
+    success!("Triggered sync for mirror {}", mirror_id);
     Ok(())
 }
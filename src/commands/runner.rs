@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use crate::cli::RunnerCommands;
+use crate::commands::not_found;
+use crate::commands::print::{print_output, print_runners};
+use crate::config::Config;
+use crate::{get_client, get_group_client};
+
+pub async fn handle(config: &mut Config, command: RunnerCommands) -> Result<()> {
+    match command {
+        RunnerCommands::List {
+            group,
+            all,
+            project,
+        } => handle_list(config, project.as_deref(), group.as_deref(), all).await,
+        RunnerCommands::Show { id } => handle_show(config, id).await,
+    }
+}
+
+async fn handle_list(
+    config: &mut Config,
+    project: Option<&str>,
+    group: Option<&str>,
+    all: bool,
+) -> Result<()> {
+    let result = if let Some(group) = group {
+        get_group_client(config)
+            .await?
+            .list_group_runners(group)
+            .await?
+    } else if all {
+        get_group_client(config).await?.list_user_runners().await?
+    } else {
+        get_client(config, project).await?.list_runners().await?
+    };
+    print_output(&result, print_runners);
+    Ok(())
+}
+
+async fn handle_show(config: &mut Config, id: u64) -> Result<()> {
+    let client = get_group_client(config).await?;
+    let result = client
+        .get_runner(id)
+        .await
+        .map_err(not_found(|| format!("Runner {} not found", id)))?;
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
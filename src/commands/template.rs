@@ -0,0 +1,97 @@
+use anyhow::{bail, Context, Result};
+
+use crate::api::Client;
+use crate::commands::edit_text;
+
+#[derive(Clone, Copy)]
+pub enum TemplateKind {
+    MergeRequest,
+    Issue,
+}
+
+impl TemplateKind {
+    fn dir(self) -> &'static str {
+        match self {
+            TemplateKind::MergeRequest => ".gitlab/merge_request_templates",
+            TemplateKind::Issue => ".gitlab/issue_templates",
+        }
+    }
+}
+
+async fn default_branch(client: &Client) -> Result<String> {
+    let project_info = client.get_project().await?;
+    Ok(project_info["default_branch"]
+        .as_str()
+        .unwrap_or("main")
+        .to_string())
+}
+
+/// Names of the description templates (without `.md`) on the default branch.
+pub async fn list_templates(client: &Client, kind: TemplateKind) -> Result<Vec<String>> {
+    let branch = default_branch(client).await?;
+    let tree = client
+        .list_repository_tree(kind.dir(), &branch)
+        .await
+        .with_context(|| format!("No templates found in {} on {}", kind.dir(), branch))?;
+    let mut names: Vec<String> = tree
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter(|e| e["type"].as_str() == Some("blob"))
+                .filter_map(|e| e["name"].as_str())
+                .filter_map(|name| name.strip_suffix(".md"))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    Ok(names)
+}
+
+pub async fn print_templates(client: &Client, kind: TemplateKind) -> Result<()> {
+    let names = list_templates(client, kind).await?;
+    if names.is_empty() {
+        println!("No templates in {}", kind.dir());
+    }
+    for name in names {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+/// Build the description from `--description`/`--template`, optionally opening
+/// the result in `$EDITOR` when `edit` is set.
+pub async fn resolve_description(
+    client: &Client,
+    kind: TemplateKind,
+    description: Option<String>,
+    template: Option<&str>,
+    edit: bool,
+) -> Result<Option<String>> {
+    let initial = match template {
+        Some(name) => {
+            let name = name.strip_suffix(".md").unwrap_or(name);
+            let branch = default_branch(client).await?;
+            let path = format!("{}/{}.md", kind.dir(), name);
+            match client.get_raw_file(&path, &branch).await {
+                Ok(text) => Some(text),
+                Err(_) => {
+                    let available = list_templates(client, kind).await.unwrap_or_default();
+                    if available.is_empty() {
+                        bail!("Template '{}' not found in {}", name, kind.dir());
+                    }
+                    bail!(
+                        "Template '{}' not found (available: {})",
+                        name,
+                        available.join(", ")
+                    );
+                }
+            }
+        }
+        None => description,
+    };
+    if edit {
+        return Ok(Some(edit_text(initial.as_deref().unwrap_or(""), "md")?));
+    }
+    Ok(initial)
+}
@@ -0,0 +1,90 @@
+use anyhow::{bail, Result};
+
+use crate::api::TimeTarget;
+use crate::cli::TimeCommands;
+use crate::commands::print::{print_output, print_time_stats};
+use crate::config::Config;
+use crate::get_client;
+
+pub async fn handle(config: &mut Config, target: TimeTarget, command: TimeCommands) -> Result<()> {
+    match command {
+        TimeCommands::Estimate {
+            iid,
+            duration,
+            project,
+        } => handle_estimate(config, project.as_deref(), target, iid, &duration).await,
+        TimeCommands::Spend {
+            iid,
+            duration,
+            project,
+        } => handle_spend(config, project.as_deref(), target, iid, &duration).await,
+        TimeCommands::Stats { iid, project } => {
+            handle_stats(config, project.as_deref(), target, iid).await
+        }
+    }
+}
+
+fn reference(target: TimeTarget, iid: u64) -> String {
+    match target {
+        TimeTarget::Issue => format!("#{}", iid),
+        TimeTarget::MergeRequest => format!("!{}", iid),
+    }
+}
+
+fn check_duration(duration: &str) -> Result<()> {
+    if duration.trim().is_empty() {
+        bail!("Duration is empty (expected e.g. 30m, 2h, 1d 4h)");
+    }
+    Ok(())
+}
+
+async fn handle_estimate(
+    config: &mut Config,
+    project: Option<&str>,
+    target: TimeTarget,
+    iid: u64,
+    duration: &str,
+) -> Result<()> {
+    check_duration(duration)?;
+    let client = get_client(config, project).await?;
+    let stats = client.set_time_estimate(target, iid, duration).await?;
+    let estimate = stats["human_time_estimate"].as_str().unwrap_or(duration);
+    success!(
+        "Estimate for {} set to {}",
+        reference(target, iid),
+        estimate
+    );
+    Ok(())
+}
+
+async fn handle_spend(
+    config: &mut Config,
+    project: Option<&str>,
+    target: TimeTarget,
+    iid: u64,
+    duration: &str,
+) -> Result<()> {
+    check_duration(duration)?;
+    let client = get_client(config, project).await?;
+    let stats = client.add_spent_time(target, iid, duration).await?;
+    let total = stats["human_total_time_spent"].as_str().unwrap_or("?");
+    success!(
+        "Logged {} on {} (total spent: {})",
+        duration,
+        reference(target, iid),
+        total
+    );
+    Ok(())
+}
+
+async fn handle_stats(
+    config: &mut Config,
+    project: Option<&str>,
+    target: TimeTarget,
+    iid: u64,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let stats = client.get_time_stats(target, iid).await?;
+    print_output(&stats, print_time_stats);
+    Ok(())
+}
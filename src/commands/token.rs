@@ -0,0 +1,74 @@
+use anyhow::{bail, Result};
+
+use crate::cli::TokenCommands;
+use crate::commands::print::{print_access_tokens, print_output};
+use crate::config::Config;
+use crate::get_client;
+
+pub async fn handle(config: &mut Config, command: TokenCommands) -> Result<()> {
+    match command {
+        TokenCommands::List { project } => handle_list(config, project.as_deref()).await,
+        TokenCommands::Create {
+            name,
+            scopes,
+            expires_at,
+            project,
+            ..
+        } => {
+            handle_create(
+                config,
+                project.as_deref(),
+                &name,
+                &scopes,
+                expires_at.as_deref(),
+            )
+            .await
+        }
+        TokenCommands::Revoke { id, project } => {
+            handle_revoke(config, project.as_deref(), id).await
+        }
+    }
+}
+
+async fn handle_list(config: &mut Config, project: Option<&str>) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client.list_project_access_tokens().await?;
+    print_output(&result, print_access_tokens);
+    Ok(())
+}
+
+async fn handle_create(
+    config: &mut Config,
+    project: Option<&str>,
+    name: &str,
+    scopes: &str,
+    expires_at: Option<&str>,
+) -> Result<()> {
+    let scopes: Vec<&str> = scopes
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    if scopes.is_empty() {
+        bail!("At least one scope is required");
+    }
+    let client = get_client(config, project).await?;
+    let result = client
+        .create_project_access_token(name, &scopes, expires_at)
+        .await?;
+    print_output(&result, |created| {
+        let id = created["id"].as_u64().unwrap_or(0);
+        let token = created["token"].as_str().unwrap_or("");
+        success!("Created access token {} ({})", id, name);
+        println!("{}", token);
+    });
+    eprintln!("Store this token now; it will not be shown again.");
+    Ok(())
+}
+
+async fn handle_revoke(config: &mut Config, project: Option<&str>, id: u64) -> Result<()> {
+    let client = get_client(config, project).await?;
+    client.revoke_project_access_token(id).await?;
+    success!("Revoked access token {}", id);
+    Ok(())
+}
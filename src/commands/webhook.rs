@@ -1,8 +1,9 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 
-use crate::api;
+use crate::api::{self, ApiError};
 use crate::cli::WebhookCommands;
-use crate::commands::print::print_webhooks;
+use crate::commands::print::{print_output, print_webhooks};
+use crate::commands::{api_message, confirm, not_found};
 use crate::config::Config;
 use crate::get_client;
 
@@ -10,63 +11,272 @@ pub async fn handle(config: &mut Config, command: WebhookCommands) -> Result<()>
     match command {
         WebhookCommands::List { project } => handle_list(config, project.as_deref()).await,
         WebhookCommands::Show { id, project } => handle_show(config, project.as_deref(), id).await,
-        WebhookCommands::Create { url, token, push, merge_request, issue, pipeline, tag, note, job, release, ssl_verification, project } => {
-            let params = api::WebhookCreateParams { url, token, push_events: push, merge_requests_events: merge_request, issues_events: issue, pipeline_events: pipeline, tag_push_events: tag, note_events: note, job_events: job, releases_events: release, enable_ssl_verification: ssl_verification };
-            handle_create(config, project.as_deref(), params).await
+        WebhookCommands::Create {
+            url,
+            token,
+            from,
+            from_project,
+            push,
+            merge_request,
+            issue,
+            pipeline,
+            tag,
+            note,
+            job,
+            release,
+            wiki_page,
+            deployment,
+            confidential_issue,
+            confidential_note,
+            branch_filter,
+            ssl_verification,
+            verify_url,
+            project,
+            ..
+        } => {
+            let mut params = api::WebhookCreateParams {
+                url: url.unwrap_or_default(),
+                token,
+                push_events: push,
+                merge_requests_events: merge_request,
+                issues_events: issue,
+                pipeline_events: pipeline,
+                tag_push_events: tag,
+                note_events: note,
+                job_events: job,
+                releases_events: release,
+                wiki_page_events: wiki_page,
+                deployment_events: deployment,
+                confidential_issues_events: confidential_issue,
+                confidential_note_events: confidential_note,
+                push_events_branch_filter: branch_filter,
+                enable_ssl_verification: ssl_verification,
+            };
+            if let Some(id) = from {
+                let source = from_project.as_deref().or(project.as_deref());
+                copy_webhook(config, source, id, &mut params).await?;
+            }
+            handle_create(config, project.as_deref(), params, verify_url).await
         }
-        WebhookCommands::Update { id, url, token, push, merge_request, issue, pipeline, tag, note, job, release, ssl_verification, project } => {
-            let params = api::WebhookUpdateParams { url, token, push_events: push, merge_requests_events: merge_request, issues_events: issue, pipeline_events: pipeline, tag_push_events: tag, note_events: note, job_events: job, releases_events: release, enable_ssl_verification: ssl_verification };
+        WebhookCommands::Update {
+            id,
+            url,
+            token,
+            push,
+            merge_request,
+            issue,
+            pipeline,
+            tag,
+            note,
+            job,
+            release,
+            ssl_verification,
+            project,
+        } => {
+            let params = api::WebhookUpdateParams {
+                url,
+                token,
+                push_events: push,
+                merge_requests_events: merge_request,
+                issues_events: issue,
+                pipeline_events: pipeline,
+                tag_push_events: tag,
+                note_events: note,
+                job_events: job,
+                releases_events: release,
+                enable_ssl_verification: ssl_verification,
+            };
             handle_update(config, project.as_deref(), id, params).await
         }
-        WebhookCommands::Delete { id, project } => handle_delete(config, project.as_deref(), id).await,
-        WebhookCommands::Test { id, event, project } => handle_test(config, project.as_deref(), id, &event).await,
+        WebhookCommands::Delete { id, yes, project } => {
+            handle_delete(config, project.as_deref(), id, yes).await
+        }
+        WebhookCommands::Test { id, event, project } => {
+            handle_test(config, project.as_deref(), id, &event).await
+        }
     }
 }
 
 async fn handle_list(config: &mut Config, project: Option<&str>) -> Result<()> {
     let client = get_client(config, project).await?;
     let result = client.list_webhooks().await?;
-    print_webhooks(&result);
+    print_output(&result, print_webhooks);
     Ok(())
 }
 
 async fn handle_show(config: &mut Config, project: Option<&str>, id: u64) -> Result<()> {
     let client = get_client(config, project).await?;
-    let result = client.get_webhook(id).await?;
+    let result = client
+        .get_webhook(id)
+        .await
+        .map_err(not_found(|| format!("Webhook {} not found", id)))?;
     println!("{}", serde_json::to_string_pretty(&result)?);
     Ok(())
 }
 
+/// `--from`: fill `params` from an existing hook. `--url`, `--branch-filter`
+/// and `--ssl-verification` replace the copied values; event flags given on the
+/// command line are added to the copied ones.
+/// GitLab never returns a hook's secret, so `--token` must be given (empty for none).
+async fn copy_webhook(
+    config: &mut Config,
+    project: Option<&str>,
+    id: u64,
+    params: &mut api::WebhookCreateParams,
+) -> Result<()> {
+    let Some(token) = params.token.take() else {
+        bail!(
+            "The secret token of webhook {} can't be copied; pass --token, or --token '' for none",
+            id
+        );
+    };
+    params.token = Some(token).filter(|t| !t.is_empty());
+
+    let client = get_client(config, project).await?;
+    let hook = client
+        .get_webhook(id)
+        .await
+        .map_err(not_found(|| format!("Webhook {} not found", id)))?;
+    if params.url.is_empty() {
+        params.url = hook["url"].as_str().unwrap_or_default().to_string();
+    }
+    let enabled = |key: &str| hook[key].as_bool().unwrap_or(false);
+    params.push_events |= enabled("push_events");
+    params.merge_requests_events |= enabled("merge_requests_events");
+    params.issues_events |= enabled("issues_events");
+    params.pipeline_events |= enabled("pipeline_events");
+    params.tag_push_events |= enabled("tag_push_events");
+    params.note_events |= enabled("note_events");
+    params.job_events |= enabled("job_events");
+    params.releases_events |= enabled("releases_events");
+    params.wiki_page_events |= enabled("wiki_page_events");
+    params.deployment_events |= enabled("deployment_events");
+    params.confidential_issues_events |= enabled("confidential_issues_events");
+    params.confidential_note_events |= enabled("confidential_note_events");
+    if params.push_events_branch_filter.is_none() {
+        params.push_events_branch_filter = hook["push_events_branch_filter"]
+            .as_str()
+            .filter(|filter| !filter.is_empty())
+            .map(str::to_string);
+    }
+    if params.enable_ssl_verification.is_none() {
+        params.enable_ssl_verification = hook["enable_ssl_verification"].as_bool();
+    }
+    Ok(())
+}
+
 async fn handle_create(
     config: &mut Config,
     project: Option<&str>,
     params: api::WebhookCreateParams,
+    verify_url: bool,
 ) -> Result<()> {
+    validate_url(&params.url)?;
+    let events = [
+        params.push_events,
+        params.merge_requests_events,
+        params.issues_events,
+        params.pipeline_events,
+        params.tag_push_events,
+        params.note_events,
+        params.job_events,
+        params.releases_events,
+        params.wiki_page_events,
+        params.deployment_events,
+        params.confidential_issues_events,
+        params.confidential_note_events,
+    ];
+    if !events.contains(&true) {
+        bail!(
+            "No events enabled; pass at least one of: {}",
+            EVENT_FLAGS.join(", ")
+        );
+    }
+    if verify_url {
+        check_reachable(&params.url, params.enable_ssl_verification.unwrap_or(true)).await?;
+    }
     let client = get_client(config, project).await?;
     let result = client.create_webhook(&params).await?;
-    let hook_id = result["id"].as_u64().unwrap_or(0);
-    let hook_url = result["url"].as_str().unwrap_or("");
-    println!("Created webhook {} -> {}", hook_id, hook_url);
+    print_output(&result, |hook| {
+        let hook_id = hook["id"].as_u64().unwrap_or(0);
+        let hook_url = hook["url"].as_str().unwrap_or("");
+        success!("Created webhook {} -> {}", hook_id, hook_url);
+    });
     Ok(())
 }
 
+/// Event flags of `webhook create`, listed when none is given.
+const EVENT_FLAGS: &[&str] = &[
+    "--push",
+    "--merge-request",
+    "--issue",
+    "--pipeline",
+    "--tag",
+    "--note",
+    "--job",
+    "--release",
+    "--wiki-page",
+    "--deployment",
+    "--confidential-issue",
+    "--confidential-note",
+];
+
+/// Reject anything but an absolute http(s) URL before GitLab stores it.
+fn validate_url(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| anyhow::anyhow!("Invalid webhook URL '{}': {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        bail!("Invalid webhook URL '{}': expected http(s)://host/...", url);
+    }
+    Ok(())
+}
+
+/// HEAD the hook URL from here. Any HTTP response counts as reachable, since
+/// receivers often reject HEAD; only connection errors and timeouts fail.
+async fn check_reachable(url: &str, ssl_verification: bool) -> Result<()> {
+    let http = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .danger_accept_invalid_certs(!ssl_verification)
+        .build()?;
+    match http.head(url).send().await {
+        Ok(response) => {
+            eprintln!("{} answered HEAD with {}", url, response.status());
+            Ok(())
+        }
+        Err(e) => {
+            Err(anyhow::Error::from(e).context(format!("Webhook URL {} is not reachable", url)))
+        }
+    }
+}
+
 async fn handle_update(
     config: &mut Config,
     project: Option<&str>,
     id: u64,
     params: api::WebhookUpdateParams,
 ) -> Result<()> {
+    if let Some(url) = &params.url {
+        validate_url(url)?;
+    }
     let client = get_client(config, project).await?;
     let result = client.update_webhook(id, &params).await?;
     let hook_url = result["url"].as_str().unwrap_or("");
-    println!("Updated webhook {} -> {}", id, hook_url);
+    success!("Updated webhook {} -> {}", id, hook_url);
     Ok(())
 }
 
-async fn handle_delete(config: &mut Config, project: Option<&str>, id: u64) -> Result<()> {
+async fn handle_delete(
+    config: &mut Config,
+    project: Option<&str>,
+    id: u64,
+    yes: bool,
+) -> Result<()> {
+    if !yes && !confirm(&format!("Delete webhook {}", id), None)? {
+        bail!("Aborted");
+    }
     let client = get_client(config, project).await?;
     client.delete_webhook(id).await?;
-    println!("Deleted webhook {}", id);
+    success!("Deleted webhook {}", id);
     Ok(())
 }
 
@@ -77,7 +287,37 @@ async fn handle_test(
     event: &str,
 ) -> Result<()> {
     let client = get_client(config, project).await?;
-    client.test_webhook(id, event).await?;
-    println!("Sent test {} event to webhook {}", event, id);
+    // GitLab answers 422 with the hook error when the receiver fails or
+    // returns non-2xx; some versions instead report `http_status` in the body
+    let result = client.test_webhook(id, event).await.map_err(|err| {
+        match err.downcast_ref::<ApiError>() {
+            Some(api_err) if api_err.status.as_u16() == 422 => anyhow::anyhow!(
+                "Test {} event to webhook {} failed: {}",
+                event,
+                id,
+                api_message(&api_err.body)
+            ),
+            _ => err,
+        }
+    })?;
+    match result["http_status"].as_u64() {
+        Some(status) if !(200..300).contains(&status) => bail!(
+            "Test {} event to webhook {} failed: receiver answered HTTP {}{}",
+            event,
+            id,
+            status,
+            result["message"]
+                .as_str()
+                .map(|m| format!(" ({})", m))
+                .unwrap_or_default()
+        ),
+        Some(status) => success!(
+            "Sent test {} event to webhook {}: receiver answered HTTP {}",
+            event,
+            id,
+            status
+        ),
+        None => success!("Sent test {} event to webhook {}: delivered", event, id),
+    }
     Ok(())
 }
@@ -0,0 +1,93 @@
+use anyhow::{bail, Context, Result};
+use std::io::Read;
+use std::path::Path;
+
+use crate::cli::WikiCommands;
+use crate::commands::not_found;
+use crate::commands::print::{print_output, print_wiki_page, print_wiki_pages};
+use crate::config::Config;
+use crate::get_client;
+
+const WIKI_FORMATS: &[&str] = &["markdown", "rdoc", "asciidoc", "org"];
+
+pub async fn handle(config: &mut Config, command: WikiCommands) -> Result<()> {
+    match command {
+        WikiCommands::List { project } => handle_list(config, project.as_deref()).await,
+        WikiCommands::Show { slug, project } => {
+            handle_show(config, project.as_deref(), &slug).await
+        }
+        WikiCommands::Create {
+            title,
+            file,
+            format,
+            project,
+            ..
+        } => handle_create(config, project.as_deref(), &title, file.as_deref(), &format).await,
+        WikiCommands::Delete { slug, project } => {
+            handle_delete(config, project.as_deref(), &slug).await
+        }
+    }
+}
+
+async fn handle_list(config: &mut Config, project: Option<&str>) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client.list_wiki_pages().await?;
+    print_output(&result, print_wiki_pages);
+    Ok(())
+}
+
+async fn handle_show(config: &mut Config, project: Option<&str>, slug: &str) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let result = client
+        .get_wiki_page(slug)
+        .await
+        .map_err(not_found(|| format!("Wiki page {} not found", slug)))?;
+    print_output(&result, print_wiki_page);
+    Ok(())
+}
+
+async fn handle_create(
+    config: &mut Config,
+    project: Option<&str>,
+    title: &str,
+    file: Option<&Path>,
+    format: &str,
+) -> Result<()> {
+    if !WIKI_FORMATS.contains(&format) {
+        bail!(
+            "Invalid format: '{}' (expected: {})",
+            format,
+            WIKI_FORMATS.join(", ")
+        );
+    }
+    let content = match file {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+        None => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            buf
+        }
+    };
+    if content.trim().is_empty() {
+        bail!("Wiki page content is empty");
+    }
+
+    let client = get_client(config, project).await?;
+    let result = client.create_wiki_page(title, &content, format).await?;
+    print_output(&result, |page| {
+        let slug = page["slug"].as_str().unwrap_or("");
+        success!("Created wiki page {} ({})", title, slug);
+    });
+    Ok(())
+}
+
+async fn handle_delete(config: &mut Config, project: Option<&str>, slug: &str) -> Result<()> {
+    let client = get_client(config, project).await?;
+    client
+        .delete_wiki_page(slug)
+        .await
+        .map_err(not_found(|| format!("Wiki page {} not found", slug)))?;
+    success!("Deleted wiki page {}", slug);
+    Ok(())
+}
@@ -1,10 +1,18 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+/// Refresh OAuth2 tokens this many seconds before they expire
+const EXPIRY_SKEW_SECS: i64 = 60;
+
+/// Per-repository settings, found in the current directory or a parent up to
+/// the git root
+pub const LOCAL_CONFIG_FILE: &str = ".gitlab-cli.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     pub host: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -12,6 +20,97 @@ pub struct Config {
     pub project: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub oauth2: Option<OAuth2Config>,
+    /// Cache group project lists for this many seconds (unset disables caching)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_cache_ttl: Option<u64>,
+    /// `--output` format used when neither the flag nor `GITLAB_CLI_OUTPUT` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_output: Option<String>,
+    /// Command shortcuts: alias name -> the arguments it expands to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// User to act as via the `Sudo` header; set from `--sudo`, never saved
+    #[serde(skip)]
+    pub sudo: Option<String>,
+    /// Extra request headers from `--header`, never saved
+    #[serde(skip)]
+    pub headers: Vec<(String, String)>,
+    /// Request log from `--log-file`, never saved
+    #[serde(skip)]
+    pub log_file: Option<PathBuf>,
+    /// Per-request limit from `--request-timeout`, never saved
+    #[serde(skip)]
+    pub request_timeout: Option<u64>,
+    /// File this config was loaded from and is saved back to
+    #[serde(skip)]
+    path: PathBuf,
+    /// Repo-local overrides merged over the file above, if one was found
+    #[serde(skip)]
+    local: Option<LocalOverride>,
+}
+
+/// Contents of a `.gitlab-cli.json`. Only `host` and `project` are read, so a
+/// committed file can't carry credentials. Its `host` is only honoured when it
+/// can't redirect them either; see `Config::apply_local`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LocalConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl LocalConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config from {:?}", path))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// The `.gitlab-cli.json` that applies to the current directory.
+    pub fn find() -> Option<PathBuf> {
+        let cwd = std::env::current_dir().ok()?;
+        Self::search_dirs(&cwd)
+            .into_iter()
+            .map(|dir| dir.join(LOCAL_CONFIG_FILE))
+            .find(|path| path.exists())
+    }
+
+    /// Where `config --local` writes: the git root, or the current directory
+    /// outside a repository.
+    pub fn target() -> Result<PathBuf> {
+        let cwd = std::env::current_dir()?;
+        let root = Self::search_dirs(&cwd).pop().unwrap_or(cwd);
+        Ok(root.join(LOCAL_CONFIG_FILE))
+    }
+
+    /// The current directory and its parents up to the git root; just the
+    /// current directory when not inside a repository.
+    fn search_dirs(cwd: &Path) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        for dir in cwd.ancestors() {
+            dirs.push(dir.to_path_buf());
+            if dir.join(".git").exists() {
+                return dirs;
+            }
+        }
+        vec![cwd.to_path_buf()]
+    }
+}
+
+/// A loaded `.gitlab-cli.json` and the user config values it shadows, so
+/// `save` writes the user config back without the repo's settings.
+#[derive(Debug, Clone)]
+struct LocalOverride {
+    path: PathBuf,
+    values: LocalConfig,
+    shadowed: LocalConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,33 +119,100 @@ pub struct OAuth2Config {
     pub access_token: String,
     pub refresh_token: String,
     pub expires_at: DateTime<Utc>,
+    /// Space-separated scopes granted to the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<String>,
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
 }
 
 impl OAuth2Config {
     pub fn is_expired(&self) -> bool {
         Utc::now() >= self.expires_at
     }
+
+    /// Expired or about to expire, so a refresh now avoids a request failing mid-flight.
+    pub fn expires_soon(&self) -> bool {
+        Utc::now() + Duration::seconds(EXPIRY_SKEW_SECS) >= self.expires_at
+    }
 }
 
 impl Config {
-    fn config_path() -> Result<PathBuf> {
+    /// Resolve the config file: `--config`, then `GITLAB_CLI_CONFIG`, then the
+    /// default location under the user config directory (`config.toml` if it
+    /// exists, otherwise `config.json`).
+    fn config_path(path_override: Option<&Path>) -> PathBuf {
+        Self::resolve_path(path_override, true)
+    }
+
+    fn resolve_path(path_override: Option<&Path>, warn: bool) -> PathBuf {
+        if let Some(path) = path_override {
+            return path.to_path_buf();
+        }
+        if let Ok(path) = std::env::var("GITLAB_CLI_CONFIG") {
+            return PathBuf::from(path);
+        }
         let config_dir = dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("gitlab-cli");
-        Ok(config_dir.join("config.json"))
+        let toml_path = config_dir.join("config.toml");
+        let json_path = config_dir.join("config.json");
+        if toml_path.exists() {
+            if warn && json_path.exists() {
+                eprintln!(
+                    "Warning: both {} and {} exist; using the TOML file",
+                    toml_path.display(),
+                    json_path.display()
+                );
+            }
+            return toml_path;
+        }
+        json_path
+    }
+
+    /// Parse config file contents, as TOML for `.toml` paths and JSON otherwise.
+    pub fn parse(path: &Path, content: &str) -> Result<Self> {
+        if is_toml(path) {
+            toml::from_str(content).context("Failed to parse config")
+        } else {
+            serde_json::from_str(content).context("Failed to parse config")
+        }
+    }
+
+    /// Aliases from the config file, read before argument parsing. Any
+    /// problem with the file is left for `load` to report.
+    pub fn load_aliases(path_override: Option<&Path>) -> BTreeMap<String, String> {
+        let path = Self::resolve_path(path_override, false);
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| Self::parse(&path, &content).ok())
+            .map(|config| config.aliases)
+            .unwrap_or_default()
     }
 
-    pub fn load() -> Result<Self> {
-        let path = Self::config_path()?;
-        let mut config = if path.exists() {
+    pub fn load(path_override: Option<&Path>) -> Result<Self> {
+        let path = Self::config_path(path_override);
+        let mut config: Self = if path.exists() {
             let content = fs::read_to_string(&path)
                 .with_context(|| format!("Failed to read config from {:?}", path))?;
-            serde_json::from_str(&content).context("Failed to parse config")?
+            Self::parse(&path, &content)?
         } else {
             Self::default()
         };
+        config.path = path;
 
-        // Environment variables override config file
+        // A repo's .gitlab-cli.json overrides the user config file
+        if let Some(local_path) = LocalConfig::find() {
+            let values = LocalConfig::load(&local_path)?;
+            let env_token = std::env::var_os("GITLAB_TOKEN").is_some();
+            if let Some(warning) = config.apply_local(local_path, values, env_token) {
+                eprintln!("Warning: {}", warning);
+            }
+        }
+
+        // Environment variables override config files
         if let Ok(token) = std::env::var("GITLAB_TOKEN") {
             config.token = Some(token);
         }
@@ -60,16 +226,92 @@ impl Config {
         Ok(config)
     }
 
+    /// Merge a repo's `.gitlab-cli.json` over the user config. A cloned repo
+    /// must not be able to send the user's token (or an OAuth refresh) to a
+    /// server it chooses, so a different `host` is only used when the token
+    /// comes from `GITLAB_TOKEN` (and no stored OAuth login would be used
+    /// instead); otherwise it is dropped and the returned warning says why.
+    fn apply_local(
+        &mut self,
+        path: PathBuf,
+        mut values: LocalConfig,
+        env_token: bool,
+    ) -> Option<String> {
+        let mut warning = None;
+        if let Some(host) = &values.host {
+            let same = host.trim_end_matches('/') == self.host().trim_end_matches('/');
+            // A stored OAuth login takes precedence over GITLAB_TOKEN
+            let token_from_env = env_token && self.oauth2.is_none();
+            if !same && !token_from_env {
+                warning = Some(format!(
+                    "Ignoring host {} from {}: it differs from the configured host {}; \
+                     set GITLAB_TOKEN to use a token for that host",
+                    host,
+                    path.display(),
+                    self.host()
+                ));
+                values.host = None;
+            }
+        }
+        let shadowed = LocalConfig {
+            host: self.host.clone(),
+            project: self.project.clone(),
+        };
+        if values.host.is_some() {
+            self.host = values.host.clone();
+        }
+        if values.project.is_some() {
+            self.project = values.project.clone();
+        }
+        self.local = Some(LocalOverride {
+            path,
+            values,
+            shadowed,
+        });
+        warning
+    }
+
     pub fn save(&self) -> Result<()> {
-        let path = Self::config_path()?;
-        if let Some(parent) = path.parent() {
+        if let Some(parent) = self.path.parent() {
             fs::create_dir_all(parent)?;
         }
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&path, content)?;
+        // Keep repo-local values out of the user config unless they were
+        // changed since loading
+        let mut on_disk = self.clone();
+        if let Some(local) = &self.local {
+            if local.values.host.is_some() && self.host == local.values.host {
+                on_disk.host = local.shadowed.host.clone();
+            }
+            if local.values.project.is_some() && self.project == local.values.project {
+                on_disk.project = local.shadowed.project.clone();
+            }
+        }
+        let content = if is_toml(&self.path) {
+            toml::to_string_pretty(&on_disk)?
+        } else {
+            serde_json::to_string_pretty(&on_disk)?
+        };
+        fs::write(&self.path, content)?;
         Ok(())
     }
 
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The `.gitlab-cli.json` merged into this config, if any.
+    pub fn local_path(&self) -> Option<&Path> {
+        self.local.as_ref().map(|local| local.path.as_path())
+    }
+
+    /// Directory for cached API data, next to the config file.
+    pub fn cache_dir(&self) -> PathBuf {
+        self.path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("cache")
+    }
+
     pub fn host(&self) -> &str {
         self.host.as_deref().unwrap_or("https://gitlab.com")
     }
@@ -83,3 +325,80 @@ impl Config {
         self.token.as_deref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_config() -> Config {
+        Config {
+            host: Some("https://gitlab.example.com".to_string()),
+            token: Some("glpat-user".to_string()),
+            project: Some("group/user".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn local(host: Option<&str>, project: Option<&str>) -> LocalConfig {
+        LocalConfig {
+            host: host.map(String::from),
+            project: project.map(String::from),
+        }
+    }
+
+    #[test]
+    fn local_project_overrides_user_config() {
+        let mut config = user_config();
+        let warning = config.apply_local(
+            PathBuf::from(".gitlab-cli.json"),
+            local(None, Some("group/repo")),
+            false,
+        );
+        assert!(warning.is_none());
+        assert_eq!(config.project.as_deref(), Some("group/repo"));
+        assert_eq!(config.host(), "https://gitlab.example.com");
+    }
+
+    #[test]
+    fn local_host_is_ignored_unless_it_matches_or_token_is_from_env() {
+        let mut config = user_config();
+        let warning = config.apply_local(
+            PathBuf::from(".gitlab-cli.json"),
+            local(Some("https://evil.example.net"), Some("group/repo")),
+            false,
+        );
+        assert!(warning.unwrap().contains("evil.example.net"));
+        assert_eq!(config.host(), "https://gitlab.example.com");
+        assert_eq!(config.project.as_deref(), Some("group/repo"));
+
+        let mut config = user_config();
+        let warning = config.apply_local(
+            PathBuf::from(".gitlab-cli.json"),
+            local(Some("https://gitlab.example.com/"), None),
+            false,
+        );
+        assert!(warning.is_none());
+        assert_eq!(config.host(), "https://gitlab.example.com/");
+
+        let mut config = user_config();
+        let warning = config.apply_local(
+            PathBuf::from(".gitlab-cli.json"),
+            local(Some("https://other.example.net"), None),
+            true,
+        );
+        assert!(warning.is_none());
+        assert_eq!(config.host(), "https://other.example.net");
+    }
+
+    #[test]
+    fn local_host_does_not_apply_over_the_default_host() {
+        let mut config = Config::default();
+        let warning = config.apply_local(
+            PathBuf::from(".gitlab-cli.json"),
+            local(Some("https://evil.example.net"), None),
+            false,
+        );
+        assert!(warning.is_some());
+        assert_eq!(config.host(), "https://gitlab.com");
+    }
+}
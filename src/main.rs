@@ -1,22 +1,35 @@
 mod api;
 mod auth;
+mod cache;
 pub mod cli;
+#[macro_use]
 mod commands;
 mod config;
+mod pager;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
-use cli::{Cli, Commands};
 use clap::Parser;
+use cli::{Cli, Commands};
+use commands::print::OutputFormat;
+use commands::{not_found, NotFound, EXIT_NOT_FOUND, EXIT_UNAUTHORIZED};
 use config::Config;
 
-pub async fn get_client(config: &mut Config, project_override: Option<&str>) -> Result<api::Client> {
+async fn ensure_fresh_token(config: &mut Config) -> Result<()> {
     if let Some(oauth2) = &config.oauth2 {
-        if oauth2.is_expired() {
-            eprintln!("Token expired, refreshing...");
+        if oauth2.expires_soon() {
+            eprintln!("Token expiring, refreshing...");
             auth::refresh_token(config).await?;
         }
     }
+    Ok(())
+}
+
+pub async fn get_client(
+    config: &mut Config,
+    project_override: Option<&str>,
+) -> Result<api::Client> {
+    ensure_fresh_token(config).await?;
 
     let token = config.get_access_token().ok_or_else(|| {
         anyhow::anyhow!("No token configured. Run: gitlab auth login --client-id <id>")
@@ -31,41 +44,285 @@ pub async fn get_client(config: &mut Config, project_override: Option<&str>) ->
             )
         })?;
 
-    api::Client::new(config.host(), token, &project)
+    api::Client::new(config.host(), token, &project)?
+        .with_token_refresh(config.clone())
+        .with_timeout(config.request_timeout.map(std::time::Duration::from_secs))
+        .with_sudo(config.sudo.as_deref())?
+        .with_headers(&config.headers)?
+        .with_log_file(config.log_file.as_deref())
 }
 
 pub async fn get_group_client(config: &mut Config) -> Result<api::Client> {
-    if let Some(oauth2) = &config.oauth2 {
-        if oauth2.is_expired() {
-            eprintln!("Token expired, refreshing...");
-            auth::refresh_token(config).await?;
-        }
-    }
+    ensure_fresh_token(config).await?;
 
     let token = config.get_access_token().ok_or_else(|| {
         anyhow::anyhow!("No token configured. Run: gitlab auth login --client-id <id>")
     })?;
 
-    api::Client::new(config.host(), token, "_")
+    api::Client::new(config.host(), token, "_")?
+        .with_token_refresh(config.clone())
+        .with_timeout(config.request_timeout.map(std::time::Duration::from_secs))
+        .with_sudo(config.sudo.as_deref())?
+        .with_headers(&config.headers)?
+        .with_log_file(config.log_file.as_deref())
+}
+
+/// Output format precedence: `--output`, then `GITLAB_CLI_OUTPUT`, then the
+/// config's `default_output`, then text.
+fn resolve_output(flag: Option<OutputFormat>, configured: Option<&str>) -> Result<OutputFormat> {
+    if let Some(format) = flag {
+        return Ok(format);
+    }
+    if let Ok(value) = std::env::var("GITLAB_CLI_OUTPUT") {
+        return value
+            .parse()
+            .map_err(|e: String| anyhow::anyhow!("GITLAB_CLI_OUTPUT: {}", e));
+    }
+    match configured {
+        Some(value) => value
+            .parse()
+            .map_err(|e: String| anyhow::anyhow!("default_output in config: {}", e)),
+        None => Ok(OutputFormat::default()),
+    }
+}
+
+/// Split `--header KEY:VALUE` arguments. Replacing the token's Authorization
+/// header needs `--force`.
+fn parse_headers(args: &[String], force: bool) -> Result<Vec<(String, String)>> {
+    args.iter()
+        .map(|arg| {
+            let (name, value) = arg.split_once(':').ok_or_else(|| {
+                anyhow::anyhow!("Invalid --header '{}' (expected KEY:VALUE)", arg)
+            })?;
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("authorization") && !force {
+                anyhow::bail!(
+                    "--header would replace the Authorization header; pass --force to allow it"
+                );
+            }
+            Ok((name.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Read-only commands whose output can run long. Anything that prompts or
+/// opens an editor must stay off the pager.
+fn wants_pager(command: &Commands) -> bool {
+    use cli::{IssueCommands, MrCommands};
+    match command {
+        Commands::Mr { command } => matches!(
+            command,
+            MrCommands::List { .. }
+                | MrCommands::Show { .. }
+                | MrCommands::Diff { .. }
+                | MrCommands::Comments { .. }
+                | MrCommands::Discussions { .. }
+        ),
+        Commands::Issue { command } => matches!(
+            command,
+            IssueCommands::List { .. }
+                | IssueCommands::Show { .. }
+                | IssueCommands::Links { .. }
+                | IssueCommands::Discussions { .. }
+        ),
+        Commands::Ci { command } => matches!(
+            command,
+            cli::CiCommands::List { .. } | cli::CiCommands::Logs { .. }
+        ),
+        Commands::Commit { command } => matches!(command, cli::CommitCommands::List { .. }),
+        Commands::Wiki { command } => matches!(command, cli::WikiCommands::Show { .. }),
+        Commands::File { .. } | Commands::Compare { .. } | Commands::Events { .. } => true,
+        Commands::Changelog { .. } => true,
+        _ => false,
+    }
+}
+
+/// `--json` on a write command is shorthand for `--output json`.
+fn json_flag(command: &Commands) -> bool {
+    use cli::{BranchCommands, CommitCommands, IssueCommands, MrCommands};
+    match command {
+        Commands::Mr { command } => matches!(
+            command,
+            MrCommands::Create { json: true, .. }
+                | MrCommands::Merge { json: true, .. }
+                | MrCommands::Close { json: true, .. }
+                | MrCommands::Comment { json: true, .. }
+                | MrCommands::Approve { json: true, .. }
+        ),
+        Commands::Issue { command } => matches!(
+            command,
+            IssueCommands::Create { json: true, .. } | IssueCommands::Update { json: true, .. }
+        ),
+        Commands::Branch { command } => {
+            matches!(command, BranchCommands::Protect { json: true, .. })
+        }
+        Commands::Commit { command } => matches!(
+            command,
+            CommitCommands::CherryPick { json: true, .. }
+                | CommitCommands::Revert { json: true, .. }
+        ),
+        Commands::Webhook { command } => {
+            matches!(command, cli::WebhookCommands::Create { json: true, .. })
+        }
+        Commands::Token { command } => {
+            matches!(command, cli::TokenCommands::Create { json: true, .. })
+        }
+        Commands::Wiki { command } => {
+            matches!(command, cli::WikiCommands::Create { json: true, .. })
+        }
+        _ => false,
+    }
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let mut config = Config::load()?;
+async fn main() {
+    if let Err(err) = run().await {
+        if err.downcast_ref::<NotFound>().is_some() {
+            eprintln!("Error: {}", err);
+            std::process::exit(EXIT_NOT_FOUND);
+        }
+        if err
+            .downcast_ref::<api::ApiError>()
+            .is_some_and(api::ApiError::is_unauthorized)
+        {
+            eprintln!("Error: Your token is invalid or expired. Run: gitlab auth login");
+            std::process::exit(EXIT_UNAUTHORIZED);
+        }
+        eprintln!("Error: {:?}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let aliases = Config::load_aliases(commands::alias::config_arg(&args).as_deref());
+    let cli = Cli::parse_from(commands::alias::expand(args, &aliases)?);
+    // Doctor loads the config itself so it can report a broken file
+    if let Commands::Doctor = cli.command {
+        let format = resolve_output(cli.output, None)?;
+        commands::print::init_output(format, cli.with_meta, cli.quiet);
+        return commands::doctor::run(cli.config.as_deref()).await;
+    }
+    let mut config = Config::load(cli.config.as_deref())?;
+    let flag = cli
+        .output
+        .or_else(|| json_flag(&cli.command).then_some(OutputFormat::Json));
+    let format = resolve_output(flag, config.default_output.as_deref())?;
+    commands::print::init_output(format, cli.with_meta, cli.quiet);
+    config.sudo = cli.sudo;
+    config.headers = parse_headers(&cli.headers, cli.force)?;
+    config.log_file = cli.log_file;
+    config.request_timeout = cli.request_timeout.filter(|&secs| secs > 0);
+    let _pager = if !cli.no_pager && wants_pager(&cli.command) {
+        pager::Pager::start()
+    } else {
+        None
+    };
 
     match cli.command {
-        Commands::Config { host, token, project } => handle_config(&mut config, host, token, project),
+        Commands::Config {
+            command: Some(cli::ConfigCommands::Edit),
+            ..
+        } => handle_config_edit(&config),
+        Commands::Config {
+            command: None,
+            host,
+            project,
+            local: true,
+            ..
+        } => handle_config_local(host, project),
+        Commands::Config {
+            command: None,
+            host,
+            token,
+            project,
+            group_cache_ttl,
+            default_output,
+            ..
+        } => handle_config(
+            &mut config,
+            host,
+            token,
+            project,
+            group_cache_ttl,
+            default_output,
+        ),
         Commands::Auth { command } => handle_auth(&mut config, command).await,
         Commands::Mr { command } => commands::mr::handle(&mut config, command).await,
         Commands::Issue { command } => commands::issue::handle(&mut config, command).await,
         Commands::Ci { command } => commands::ci::handle(&mut config, command).await,
         Commands::Group { command } => commands::group::handle(&mut config, command).await,
         Commands::Project { command } => commands::project::handle(&mut config, command).await,
+        Commands::Runner { command } => commands::runner::handle(&mut config, command).await,
         Commands::Webhook { command } => commands::webhook::handle(&mut config, command).await,
+        Commands::Wiki { command } => commands::wiki::handle(&mut config, command).await,
+        Commands::Label { command } => commands::label::handle(&mut config, command).await,
+        Commands::Commit { command } => commands::commit::handle(&mut config, command).await,
         Commands::Branch { command } => commands::branch::handle(&mut config, command).await,
-        Commands::File { path, project, git_ref } => handle_file(&mut config, path, project, git_ref).await,
-        Commands::Api { endpoint, method, data } => handle_api(&mut config, endpoint, method, data).await,
+        Commands::Token { command } => commands::token::handle(&mut config, command).await,
+        Commands::Events {
+            all,
+            after,
+            before,
+            per_page,
+            project,
+        } => {
+            handle_events(
+                &mut config,
+                all,
+                after,
+                before,
+                per_page,
+                project.as_deref(),
+            )
+            .await
+        }
+        Commands::Upload { path, project } => {
+            handle_upload(&mut config, &path, project.as_deref()).await
+        }
+        Commands::File {
+            command:
+                Some(cli::FileCommands::History {
+                    path,
+                    git_ref,
+                    per_page,
+                    project,
+                }),
+            ..
+        } => handle_file_history(&mut config, path, project, git_ref, per_page).await,
+        Commands::File {
+            command: None,
+            path,
+            project,
+            git_ref,
+        } => {
+            let Some(path) = path else {
+                unreachable!("clap requires a path without a subcommand")
+            };
+            handle_file(&mut config, path, project, git_ref).await
+        }
+        Commands::Compare { from, to, project } => {
+            handle_compare(&mut config, from, to, project).await
+        }
+        Commands::Changelog {
+            since,
+            to,
+            commits,
+            project,
+        } => handle_changelog(&mut config, &since, to, commits, project.as_deref()).await,
+        Commands::Alias { command } => commands::alias::handle(&mut config, command),
+        Commands::Cache {
+            command: cli::CacheCommands::Clear,
+        } => handle_cache_clear(&config),
+        Commands::Version => handle_version(&mut config).await,
+        Commands::Doctor => unreachable!("handled before loading config"),
+        Commands::Api {
+            endpoint,
+            method,
+            data,
+            form,
+        } => handle_api(&mut config, endpoint, method, data, form).await,
+        Commands::Graphql { query, vars } => handle_graphql(&mut config, &query, &vars).await,
     }
 }
 
@@ -74,9 +331,19 @@ fn handle_config(
     host: Option<String>,
     token: Option<String>,
     project: Option<String>,
+    group_cache_ttl: Option<u64>,
+    default_output: Option<String>,
 ) -> Result<()> {
-    if host.is_none() && token.is_none() && project.is_none() {
+    if host.is_none()
+        && token.is_none()
+        && project.is_none()
+        && group_cache_ttl.is_none()
+        && default_output.is_none()
+    {
         println!("Current configuration:");
+        if let Some(local) = config.local_path() {
+            println!("  local: {}", local.display());
+        }
         println!("  host: {}", config.host());
         println!(
             "  token: {}",
@@ -90,6 +357,17 @@ fn handle_config(
             "  project: {}",
             config.project.as_deref().unwrap_or("(not set)")
         );
+        println!(
+            "  group_cache_ttl: {}",
+            config
+                .group_cache_ttl
+                .map(|t| format!("{}s", t))
+                .unwrap_or_else(|| "(disabled)".to_string())
+        );
+        println!(
+            "  default_output: {}",
+            config.default_output.as_deref().unwrap_or("text")
+        );
         return Ok(());
     }
     if let Some(h) = host {
@@ -101,20 +379,80 @@ fn handle_config(
     if let Some(p) = project {
         config.project = Some(p);
     }
+    if let Some(ttl) = group_cache_ttl {
+        config.group_cache_ttl = (ttl > 0).then_some(ttl);
+    }
+    if let Some(format) = default_output {
+        let parsed: OutputFormat = format.parse().map_err(anyhow::Error::msg)?;
+        config.default_output = (parsed != OutputFormat::Text).then_some(format);
+    }
     config.save()?;
-    println!("Configuration saved.");
+    success!("Configuration saved.");
+    Ok(())
+}
+
+fn handle_config_local(host: Option<String>, project: Option<String>) -> Result<()> {
+    if host.is_none() && project.is_none() {
+        anyhow::bail!("--local needs --host or --project");
+    }
+    let path = config::LocalConfig::target()?;
+    let mut local = if path.exists() {
+        config::LocalConfig::load(&path)?
+    } else {
+        config::LocalConfig::default()
+    };
+    if host.is_some() {
+        local.host = host;
+    }
+    if project.is_some() {
+        local.project = project;
+    }
+    local.save(&path)?;
+    success!("Saved {}", path.display());
+    Ok(())
+}
+
+fn handle_cache_clear(config: &Config) -> Result<()> {
+    let removed = cache::clear(&config.cache_dir())?;
+    success!("Removed {} cache file(s)", removed);
+    Ok(())
+}
+
+fn handle_config_edit(config: &Config) -> Result<()> {
+    let path = config.path();
+    let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+    let original = if path.exists() {
+        std::fs::read_to_string(path)?
+    } else if is_toml {
+        String::new()
+    } else {
+        "{}\n".to_string()
+    };
+    let extension = if is_toml { "toml" } else { "json" };
+    let edited = commands::edit_text(&original, extension)?;
+    if edited == original {
+        println!("No changes.");
+        return Ok(());
+    }
+    Config::parse(path, &edited).context("Invalid config, keeping the previous file")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, edited)?;
+    success!("Configuration saved to {}", path.display());
     Ok(())
 }
 
 async fn handle_auth(config: &mut Config, command: cli::AuthCommands) -> Result<()> {
     match command {
-        cli::AuthCommands::Login { client_id, host } => {
-            handle_auth_login(config, client_id, host).await
-        }
-        cli::AuthCommands::Status => {
-            print_auth_status(config);
-            Ok(())
-        }
+        cli::AuthCommands::Login {
+            client_id,
+            host,
+            redirect_port,
+            device,
+            scopes,
+        } => handle_auth_login(config, client_id, host, redirect_port, device, &scopes).await,
+        cli::AuthCommands::Status => print_auth_status(config).await,
     }
 }
 
@@ -122,10 +460,39 @@ async fn handle_auth_login(
     config: &mut Config,
     client_id: Option<String>,
     host: Option<String>,
+    redirect_port: u16,
+    device: bool,
+    scopes: &str,
 ) -> Result<()> {
+    let scopes = scopes.split_whitespace().collect::<Vec<_>>().join(" ");
+    if scopes.is_empty() {
+        anyhow::bail!("--scopes must list at least one scope");
+    }
     let auth_host = host.as_deref().unwrap_or_else(|| config.host());
     let cid = client_id.as_deref().unwrap_or(auth::default_client_id());
-    let flow = auth::AuthFlow::new(auth_host, cid);
+
+    let oauth2_config = if device {
+        auth::device_login(auth_host, cid, &scopes).await?
+    } else {
+        browser_login(auth_host, cid, redirect_port, &scopes).await?
+    };
+    config.oauth2 = Some(oauth2_config);
+    config.token = None;
+    if host.is_some() {
+        config.host = host;
+    }
+    config.save()?;
+    println!("Authentication successful!");
+    Ok(())
+}
+
+async fn browser_login(
+    host: &str,
+    client_id: &str,
+    redirect_port: u16,
+    scopes: &str,
+) -> Result<config::OAuth2Config> {
+    let flow = auth::AuthFlow::new(host, client_id, redirect_port, scopes);
 
     let auth_url = flow.authorization_url();
     println!("Opening browser for authorization...");
@@ -138,18 +505,10 @@ async fn handle_auth_login(
     let code = flow.wait_for_callback()?;
     println!("Authorization code received, exchanging for token...");
 
-    let oauth2_config = flow.exchange_code(&code).await?;
-    config.oauth2 = Some(oauth2_config);
-    config.token = None;
-    if host.is_some() {
-        config.host = host;
-    }
-    config.save()?;
-    println!("Authentication successful!");
-    Ok(())
+    flow.exchange_code(&code).await
 }
 
-fn print_auth_status(config: &Config) {
+async fn print_auth_status(config: &mut Config) -> Result<()> {
     if let Some(oauth2) = &config.oauth2 {
         println!("OAuth2 authenticated");
         println!(
@@ -157,12 +516,33 @@ fn print_auth_status(config: &Config) {
             &oauth2.client_id[..8.min(oauth2.client_id.len())]
         );
         println!("  expires_at: {}", oauth2.expires_at);
+        println!(
+            "  scopes: {}",
+            oauth2.scopes.as_deref().unwrap_or("(unknown)")
+        );
         println!("  expired: {}", oauth2.is_expired());
     } else if config.token.is_some() {
         println!("Using static token (legacy)");
+        let client = get_group_client(config).await?;
+        match client.get_current_token().await {
+            Ok(token) => {
+                let scopes: Vec<&str> = token["scopes"]
+                    .as_array()
+                    .map(|arr| arr.iter().filter_map(|s| s.as_str()).collect())
+                    .unwrap_or_default();
+                println!("  name: {}", token["name"].as_str().unwrap_or("?"));
+                println!(
+                    "  expires_at: {}",
+                    token["expires_at"].as_str().unwrap_or("never")
+                );
+                println!("  scopes: {}", scopes.join(" "));
+            }
+            Err(e) => println!("  scopes: (unavailable: {})", e),
+        }
     } else {
         println!("Not authenticated");
     }
+    Ok(())
 }
 
 async fn handle_file(
@@ -182,21 +562,225 @@ async fn handle_file(
                 .to_string()
         }
     };
-    let content = client.get_raw_file(&path, &ref_name).await?;
+    let content = client
+        .get_raw_file(&path, &ref_name)
+        .await
+        .map_err(not_found(|| {
+            format!("File {} not found at {}", path, ref_name)
+        }))?;
     print!("{}", content);
     Ok(())
 }
 
+async fn handle_events(
+    config: &mut Config,
+    all: bool,
+    after: Option<String>,
+    before: Option<String>,
+    per_page: u32,
+    project: Option<&str>,
+) -> Result<()> {
+    for (flag, value) in [("--after", &after), ("--before", &before)] {
+        if let Some(date) = value {
+            commands::check_day(flag, date)?;
+        }
+    }
+    let (client, scope) = if all {
+        (
+            get_group_client(config).await?,
+            api::EventScope::CurrentUser,
+        )
+    } else {
+        (get_client(config, project).await?, api::EventScope::Project)
+    };
+    let (events, headers) = client
+        .list_events(scope, per_page, after.as_deref(), before.as_deref())
+        .await?;
+    commands::print::print_page(&events, &headers, commands::print::print_events);
+    Ok(())
+}
+
+async fn handle_upload(
+    config: &mut Config,
+    path: &std::path::Path,
+    project: Option<&str>,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let upload = client.upload_file(path).await?;
+    let markdown = upload["markdown"]
+        .as_str()
+        .context("Upload response has no markdown link")?;
+    println!("{}", markdown);
+    Ok(())
+}
+
+async fn handle_file_history(
+    config: &mut Config,
+    path: String,
+    project: Option<String>,
+    git_ref: Option<String>,
+    per_page: u32,
+) -> Result<()> {
+    let client = get_client(config, project.as_deref()).await?;
+    let params = api::CommitListParams {
+        per_page,
+        ref_name: git_ref,
+        author: None,
+        since: None,
+        until: None,
+        path: Some(path),
+    };
+    let (result, headers) = client.list_commits(&params).await?;
+    commands::print::print_page(&result, &headers, commands::print::print_commits);
+    Ok(())
+}
+
+async fn handle_compare(
+    config: &mut Config,
+    from: String,
+    to: String,
+    project: Option<String>,
+) -> Result<()> {
+    let client = get_client(config, project.as_deref()).await?;
+    let result = client.compare_refs(&from, &to).await?;
+    let commits = &result["commits"];
+    if commits.as_array().map(|c| c.is_empty()).unwrap_or(true) {
+        println!("No commits between {} and {}", from, to);
+        return Ok(());
+    }
+    commands::print::print_commits(commits);
+    println!();
+    commands::print::print_diff_stat(&result["diffs"]);
+    Ok(())
+}
+
+async fn handle_changelog(
+    config: &mut Config,
+    since: &str,
+    to: Option<String>,
+    commits_only: bool,
+    project: Option<&str>,
+) -> Result<()> {
+    let client = get_client(config, project).await?;
+    let to = match to {
+        Some(to) => to,
+        None => client.get_project().await?["default_branch"]
+            .as_str()
+            .unwrap_or("main")
+            .to_string(),
+    };
+    let result = client
+        .compare_refs(since, &to)
+        .await
+        .map_err(not_found(|| format!("Ref {} or {} not found", since, to)))?;
+    let commits = result["commits"].as_array().cloned().unwrap_or_default();
+    let is_merge = |commit: &&serde_json::Value| {
+        commit["parent_ids"]
+            .as_array()
+            .is_some_and(|parents| parents.len() > 1)
+    };
+
+    if commits_only {
+        let commits: Vec<serde_json::Value> =
+            commits.iter().filter(|c| !is_merge(c)).cloned().collect();
+        let commits = serde_json::Value::Array(commits);
+        commands::print::print_output(&commits, commands::print::print_changelog_commits);
+        return Ok(());
+    }
+
+    // Merge commits name their MR directly; a fast-forward history has none,
+    // so fall back to looking up every commit.
+    let merges: Vec<&serde_json::Value> = commits.iter().filter(is_merge).collect();
+    let lookups = if merges.is_empty() {
+        commits.iter().collect()
+    } else {
+        merges
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut mrs = Vec::new();
+    for commit in lookups {
+        let Some(sha) = commit["id"].as_str() else {
+            continue;
+        };
+        let found = client.list_commit_merge_requests(sha).await?;
+        for mr in found.as_array().into_iter().flatten() {
+            if mr["state"] == "merged" && seen.insert(mr["iid"].as_u64()) {
+                mrs.push(mr.clone());
+            }
+        }
+    }
+    if mrs.is_empty() && !commits.is_empty() {
+        eprintln!(
+            "No merged MRs between {} and {}; use --commits to list commits",
+            since, to
+        );
+    }
+    let mrs = serde_json::Value::Array(mrs);
+    commands::print::print_output(&mrs, commands::print::print_changelog_mrs);
+    Ok(())
+}
+
+async fn handle_version(config: &mut Config) -> Result<()> {
+    println!("gitlab-cli {}", env!("CARGO_PKG_VERSION"));
+    if config.oauth2.is_none() && config.token.is_none() {
+        return Ok(());
+    }
+    let client = get_group_client(config).await?;
+    let result = client.get_gitlab_version().await?;
+    println!(
+        "GitLab {} ({}) at {}",
+        result["version"].as_str().unwrap_or("unknown"),
+        result["revision"].as_str().unwrap_or("?"),
+        config.host()
+    );
+    Ok(())
+}
+
 async fn handle_api(
     config: &mut Config,
     endpoint: String,
     method: String,
     data: Option<String>,
+    form: Vec<String>,
 ) -> Result<()> {
+    let form = form
+        .iter()
+        .map(|field| {
+            field
+                .split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .with_context(|| format!("Invalid --form '{}' (expected key=value)", field))
+        })
+        .collect::<Result<Vec<_>>>()?;
     let client = get_group_client(config).await?;
     let body = client
-        .raw_request(&method, &endpoint, data.as_deref())
+        .raw_request(&method, &endpoint, data.as_deref(), &form)
         .await?;
     println!("{}", body);
     Ok(())
 }
+
+async fn handle_graphql(config: &mut Config, query: &str, vars: &[String]) -> Result<()> {
+    let query = match query.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read query from {}", path))?,
+        None => query.to_string(),
+    };
+    let mut variables = serde_json::Map::new();
+    for var in vars {
+        let (key, value) = var
+            .split_once('=')
+            .with_context(|| format!("Invalid --var '{}' (expected key=value)", var))?;
+        let value = serde_json::from_str(value)
+            .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+        variables.insert(key.to_string(), value);
+    }
+
+    let client = get_group_client(config).await?;
+    let response = client.graphql(&query, &variables.into()).await?;
+    println!("{}", serde_json::to_string_pretty(&response)?);
+    if let Some(errors) = response["errors"].as_array().filter(|e| !e.is_empty()) {
+        anyhow::bail!("GraphQL query returned {} error(s)", errors.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::io::{IsTerminal, Write};
+use std::os::fd::AsRawFd;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const DEFAULT_PAGER: &str = "less -FRX";
+
+static PAGING: AtomicBool = AtomicBool::new(false);
+
+/// Whether output ends up on a terminal, directly or through the pager.
+/// Use this instead of `stdout().is_terminal()` when deciding on color.
+pub fn stdout_is_terminal() -> bool {
+    PAGING.load(Ordering::Relaxed) || std::io::stdout().is_terminal()
+}
+
+/// Pipes this process's stdout through `$GITLAB_CLI_PAGER`/`$PAGER` (default
+/// `less -FRX`) until dropped, the way git pages long output. `-F` makes less
+/// exit immediately when everything fits on one screen.
+pub struct Pager {
+    child: Child,
+    saved_stdout: libc::c_int,
+}
+
+impl Pager {
+    /// Start the pager if stdout is a terminal and a pager is configured.
+    /// Returns `None` (leaving stdout untouched) when paging doesn't apply.
+    pub fn start() -> Option<Pager> {
+        if !std::io::stdout().is_terminal() {
+            return None;
+        }
+        let command = std::env::var("GITLAB_CLI_PAGER")
+            .or_else(|_| std::env::var("PAGER"))
+            .unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+        let command = command.trim();
+        if command.is_empty() || command == "cat" {
+            return None;
+        }
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env(
+                "LESS",
+                std::env::var("LESS").unwrap_or_else(|_| "FRX".to_string()),
+            )
+            .stdin(Stdio::piped())
+            .spawn()
+            .ok()?;
+        let pipe = child.stdin.take()?;
+
+        // SAFETY: plain fd juggling on descriptors we own; the pipe end is
+        // closed once it has been duplicated onto stdout.
+        let saved_stdout = unsafe {
+            let saved = libc::dup(libc::STDOUT_FILENO);
+            if saved < 0 || libc::dup2(pipe.as_raw_fd(), libc::STDOUT_FILENO) < 0 {
+                return None;
+            }
+            saved
+        };
+        drop(pipe);
+        // Quitting the pager early should end the command quietly instead of
+        // panicking on a broken pipe.
+        unsafe {
+            libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+        }
+        PAGING.store(true, Ordering::Relaxed);
+        Some(Pager {
+            child,
+            saved_stdout,
+        })
+    }
+}
+
+impl Drop for Pager {
+    fn drop(&mut self) {
+        let _ = std::io::stdout().flush();
+        // Restoring the original stdout closes the last write end of the
+        // pipe, so the pager sees EOF and can be waited on.
+        unsafe {
+            libc::dup2(self.saved_stdout, libc::STDOUT_FILENO);
+            libc::close(self.saved_stdout);
+        }
+        let _ = self.child.wait();
+    }
+}